@@ -0,0 +1,55 @@
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use oca_sdk_rs::{
+    build_from_ocafile,
+    data_validator::{validate_data, DataValidator},
+};
+use serde_json::{json, Value};
+
+const OCAFILE: &str = r#"ADD ATTRIBUTE name=Text age=Numeric email=Text country=Text born=DateTime
+ADD META en PROPS name="Person"
+ADD LABEL en ATTRS name="Name" age="Age" email="Email" country="Country" born="Born"
+ADD ENTRY_CODE ATTRS country=["PL", "DE", "FR"]
+ADD FORMAT ATTRS email="^[^@]+@[^@]+$" born="YYYY-MM-DD"
+ADD CONFORMANCE ATTRS name=M age=M email=O country=M"#;
+
+fn records() -> Vec<Value> {
+    (0..100)
+        .map(|i| {
+            json!({
+                "name": format!("Person {i}"),
+                "age": i,
+                "email": format!("person{i}@example.com"),
+                "country": ["PL", "DE", "FR"][i % 3],
+                "born": "1990-01-01",
+            })
+        })
+        .collect()
+}
+
+/// Compares validating a batch of records with a [`DataValidator`] prepared once against
+/// calling [`validate_data`], which prepares the bundle, for every record.
+fn validate_records(c: &mut Criterion) {
+    let bundle = build_from_ocafile(OCAFILE.to_string()).unwrap();
+    let records = records();
+
+    let mut group = c.benchmark_group("validate 100 records");
+    group.bench_function("validate_data", |b| {
+        b.iter(|| {
+            for record in &records {
+                black_box(validate_data(&bundle, record).unwrap());
+            }
+        })
+    });
+    group.bench_function("DataValidator", |b| {
+        b.iter(|| {
+            let validator = DataValidator::new(&bundle).unwrap();
+            for record in &records {
+                black_box(validator.validate(record).unwrap());
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group!(benches, validate_records);
+criterion_main!(benches);
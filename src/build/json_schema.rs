@@ -0,0 +1,208 @@
+use crate::OcaSdkError;
+use oca_bundle_semantics::state::oca::OCABundle;
+use oca_rs::facade::build::build_from_ocafile;
+use serde_json::{json, Map, Value};
+
+/// Builds an OCA Bundle from a JSON Schema (draft-07 or 2020-12) of an object, the reverse of
+/// [`to_json_schema`](crate::conversion::to_json_schema).
+///
+/// Each of the `properties` becomes an attribute of the type inferred from its JSON Schema
+/// type:
+/// * `string` - `Text`, `DateTime` for the `date-time` and `date` formats, `Binary` for the
+///   `base64` content encoding,
+/// * `number` and `integer` - `Numeric`,
+/// * `boolean` - `Boolean`,
+/// * `array` - an array of the `items` type.
+///
+/// Properties listed as `required` are mandatory. The `title` and `description` of the
+/// schema become the `name` and `description` of an English meta overlay. String `enum`s
+/// become entry codes, while `pattern`, `minimum`, `maximum`, integers and the `email`,
+/// `uri`, `iri`, `uuid` and `date` formats are kept in the format overlay.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build::build_from_json_schema, WithInfo};
+///
+/// let bundle = build_from_json_schema(r#"{
+///     "title": "Person",
+///     "type": "object",
+///     "properties": {"name": {"type": "string"}, "age": {"type": "integer"}},
+///     "required": ["name"]
+/// }"#).unwrap();
+/// let info = bundle.info();
+/// assert_eq!(info.attribute("name").unwrap().conformance.as_deref(), Some("M"));
+/// assert_eq!(info.schema_name("eng"), Some("Person"));
+/// ```
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Json)` if the schema is not valid JSON.
+/// * Returns `Err(OcaSdkError::JsonSchema)` if the schema uses constructs which can't be
+///   expressed as attributes, e.g. nested objects or `$ref`s, or property names which
+///   aren't valid attribute names.
+pub fn build_from_json_schema(json_schema: &str) -> Result<OCABundle, OcaSdkError> {
+    let schema: Value = serde_json::from_str(json_schema)?;
+    let properties = match schema.get("properties") {
+        Some(Value::Object(properties)) if !properties.is_empty() => properties,
+        _ => {
+            return Err(OcaSdkError::JsonSchema(
+                "schema has no properties".to_string(),
+            ))
+        }
+    };
+    let required: Vec<&str> = schema
+        .get("required")
+        .and_then(Value::as_array)
+        .into_iter()
+        .flatten()
+        .filter_map(Value::as_str)
+        .collect();
+
+    let mut declarations = vec![];
+    let mut mandatory = vec![];
+    let mut entry_codes = vec![];
+    let mut formats = vec![];
+    for (name, property) in properties {
+        if name.is_empty()
+            || !name
+                .chars()
+                .all(|c| c.is_ascii_alphanumeric() || matches!(c, '-' | '_' | '.'))
+        {
+            return Err(OcaSdkError::JsonSchema(format!(
+                "property \"{}\" is not a valid attribute name",
+                name
+            )));
+        }
+        declarations.push(format!("{}={}", name, attribute_type(name, property)?));
+        if required.contains(&name.as_str()) {
+            mandatory.push(format!("{}=M", name));
+        }
+        if let Some(codes) = enum_codes(property) {
+            entry_codes.push(format!("{}={}", name, json!(codes)));
+        }
+        if let Some(format) = format(property) {
+            formats.push(format!("{}={}", name, quote(name, &format)?));
+        }
+    }
+
+    let mut ocafile = format!("ADD ATTRIBUTE {}\n", declarations.join(" "));
+    let mut meta = vec![];
+    for (key, meta_key) in [("title", "name"), ("description", "description")] {
+        if let Some(value) = schema.get(key).and_then(Value::as_str) {
+            meta.push(format!("{}={}", meta_key, quote(key, value)?));
+        }
+    }
+    if !meta.is_empty() {
+        ocafile.push_str(&format!("ADD META en PROPS {}\n", meta.join(" ")));
+    }
+    for (command, entries) in [
+        ("ADD CONFORMANCE ATTRS", mandatory),
+        ("ADD ENTRY_CODE ATTRS", entry_codes),
+        ("ADD FORMAT ATTRS", formats),
+    ] {
+        if !entries.is_empty() {
+            ocafile.push_str(&format!("{} {}\n", command, entries.join(" ")));
+        }
+    }
+
+    build_from_ocafile(ocafile).map_err(|e| OcaSdkError::Bundle(e.to_string()))
+}
+
+/// Returns the OCAFile attribute type of the property.
+fn attribute_type(name: &str, property: &Value) -> Result<String, OcaSdkError> {
+    let attribute_type = match types(property).as_slice() {
+        ["string"] => {
+            match (
+                property.get("format").and_then(Value::as_str),
+                property.get("contentEncoding").and_then(Value::as_str),
+            ) {
+                (Some("date-time" | "date"), _) => "DateTime".to_string(),
+                (_, Some("base64")) => "Binary".to_string(),
+                _ => "Text".to_string(),
+            }
+        }
+        ["number" | "integer"] => "Numeric".to_string(),
+        ["boolean"] => "Boolean".to_string(),
+        ["array"] => match property.get("items") {
+            Some(items) if items.is_object() => format!("Array[{}]", attribute_type(name, items)?),
+            _ => {
+                return Err(OcaSdkError::JsonSchema(format!(
+                    "array property \"{}\" has no items schema",
+                    name
+                )))
+            }
+        },
+        _ => {
+            return Err(OcaSdkError::JsonSchema(format!(
+                "property \"{}\" has no supported type",
+                name
+            )))
+        }
+    };
+
+    Ok(attribute_type)
+}
+
+/// Returns the JSON Schema types of the property, without `null` of nullable properties, e.g.
+/// `["string", "null"]`.
+fn types(property: &Value) -> Vec<&str> {
+    match property.get("type") {
+        Some(Value::String(t)) => vec![t.as_str()],
+        Some(Value::Array(types)) => types
+            .iter()
+            .filter_map(Value::as_str)
+            .filter(|t| *t != "null")
+            .collect(),
+        _ => vec![],
+    }
+}
+
+/// Returns the string `enum` of the property, or of the items of an array property.
+fn enum_codes(property: &Value) -> Option<Vec<&str>> {
+    let codes = property
+        .get("enum")
+        .or_else(|| property.get("items")?.get("enum"))?
+        .as_array()?;
+    codes.iter().map(Value::as_str).collect()
+}
+
+/// Returns the format overlay value of the property, i.e. a pattern, a named format or JSON
+/// options of numbers.
+fn format(property: &Value) -> Option<String> {
+    let get = |key: &str| property.get(key);
+    if let Some(pattern) = get("pattern").and_then(Value::as_str) {
+        return Some(pattern.to_string());
+    }
+    match get("format").and_then(Value::as_str) {
+        Some("email") => return Some("email".to_string()),
+        Some("uri") => return Some("URI".to_string()),
+        Some("iri") => return Some("IRI".to_string()),
+        Some("uuid") => return Some("UUID".to_string()),
+        Some("date") => return Some("YYYY-MM-DD".to_string()),
+        _ => {}
+    }
+
+    let mut options = Map::new();
+    if types(property) == ["integer"] {
+        options.insert("integer".to_string(), json!(true));
+    }
+    for (key, option) in [("minimum", "min"), ("maximum", "max")] {
+        if let Some(bound) = get(key).filter(|bound| bound.is_number()) {
+            options.insert(option.to_string(), bound.clone());
+        }
+    }
+    (!options.is_empty()).then(|| Value::Object(options).to_string())
+}
+
+/// Quotes the value as an OCAFile string, which has no escaping of quotes.
+fn quote(name: &str, value: &str) -> Result<String, OcaSdkError> {
+    if !value.contains('"') {
+        Ok(format!("\"{}\"", value))
+    } else if !value.contains('\'') {
+        Ok(format!("'{}'", value))
+    } else {
+        Err(OcaSdkError::JsonSchema(format!(
+            "value of \"{}\" contains both single and double quotes",
+            name
+        )))
+    }
+}
@@ -0,0 +1,492 @@
+//! Building OCA Bundles from OCAFile with additional checks on top of
+//! [`build_from_ocafile`](crate::build_from_ocafile), and generating OCAFile back from bundles.
+use crate::OcaSdkError;
+pub use oca_ast_semantics::ast::{CommandType, NestedValue, ObjectKind};
+use oca_ast_semantics::ast::{OCAAst, OverlayType};
+use oca_bundle_semantics::state::oca::OCABundle;
+use oca_file::ocafile::{parse_from_string, OCAAst as ParsedOCAAst};
+use oca_rs::facade::build::{
+    build_from_ocafile, parse_oca_bundle_to_ocafile, Error as OcafileError,
+};
+use std::collections::{HashMap, HashSet};
+use std::fmt;
+
+mod json_schema;
+pub use json_schema::build_from_json_schema;
+
+/// Character set names from the IANA Character Sets registry accepted in character
+/// encoding directives. Names are compared case-insensitively.
+const IANA_CHARACTER_SETS: &[&str] = &[
+    "US-ASCII",
+    "UTF-8",
+    "UTF-16",
+    "UTF-16BE",
+    "UTF-16LE",
+    "UTF-32",
+    "UTF-32BE",
+    "UTF-32LE",
+    "ISO-8859-1",
+    "ISO-8859-2",
+    "ISO-8859-3",
+    "ISO-8859-4",
+    "ISO-8859-5",
+    "ISO-8859-6",
+    "ISO-8859-7",
+    "ISO-8859-8",
+    "ISO-8859-9",
+    "ISO-8859-10",
+    "ISO-8859-13",
+    "ISO-8859-14",
+    "ISO-8859-15",
+    "ISO-8859-16",
+    "windows-1250",
+    "windows-1251",
+    "windows-1252",
+    "windows-1253",
+    "windows-1254",
+    "windows-1255",
+    "windows-1256",
+    "windows-1257",
+    "windows-1258",
+    "KOI8-R",
+    "KOI8-U",
+    "IBM437",
+    "IBM850",
+    "IBM866",
+    "macintosh",
+    "Shift_JIS",
+    "EUC-JP",
+    "ISO-2022-JP",
+    "EUC-KR",
+    "ISO-2022-KR",
+    "GBK",
+    "GB2312",
+    "GB18030",
+    "Big5",
+    "TIS-620",
+];
+
+/// Options of [`build_from_ocafile_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct BuildOptions {
+    /// Treats build warnings as errors.
+    pub strict: bool,
+    /// Version of the OCA specification the overlays used in the OCAFile have to be part of.
+    pub oca_spec_version: OcaSpecVersion,
+}
+
+/// Versions of the OCA specification, ordered from the oldest.
+///
+/// Only versions whose overlay types are known are listed: an overlay type is checked against
+/// the "Overlays" section of each release of the specification,
+/// <https://oca.colossi.network/specification/>. No overlay type has been removed so far, so
+/// only the version introducing it matters.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord)]
+pub enum OcaSpecVersion {
+    /// Defines the semantic (character encoding, encoding, format, information, label, meta,
+    /// standard), input (cardinality, conditional, conformance, entry code, entry, unit),
+    /// transformation (attribute mapping, entry code mapping, subset, unit mapping) and
+    /// presentation (layout, sensitivity) overlays.
+    V1_0,
+    /// Adds the link and attribute framing overlays. Bundles are built according to this
+    /// version, whose identifiers the overlay types carry, e.g. `spec/overlays/label/1.1`.
+    #[default]
+    V1_1,
+}
+
+impl OcaSpecVersion {
+    /// Returns the first version of the specification defining the overlay type, see
+    /// [`OcaSpecVersion`] for the overlay types of each version.
+    pub fn introducing(overlay_type: &OverlayType) -> Self {
+        match overlay_type {
+            OverlayType::CharacterEncoding(_)
+            | OverlayType::Encoding(_)
+            | OverlayType::Format(_)
+            | OverlayType::Information(_)
+            | OverlayType::Label(_)
+            | OverlayType::Meta(_)
+            | OverlayType::Standard(_)
+            | OverlayType::Cardinality(_)
+            | OverlayType::Conditional(_)
+            | OverlayType::Conformance(_)
+            | OverlayType::EntryCode(_)
+            | OverlayType::Entry(_)
+            | OverlayType::Unit(_)
+            | OverlayType::AttributeMapping(_)
+            | OverlayType::EntryCodeMapping(_)
+            | OverlayType::Subset(_)
+            | OverlayType::UnitMapping(_)
+            | OverlayType::Layout(_)
+            | OverlayType::Sensitivity(_) => OcaSpecVersion::V1_0,
+            OverlayType::Link(_) | OverlayType::AttributeFraming(_) => OcaSpecVersion::V1_1,
+        }
+    }
+}
+
+impl fmt::Display for OcaSpecVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OcaSpecVersion::V1_0 => write!(f, "1.0"),
+            OcaSpecVersion::V1_1 => write!(f, "1.1"),
+        }
+    }
+}
+
+/// Bundle built by [`build_from_ocafile_with_options`] along with findings which didn't
+/// prevent building it.
+pub struct BuildResult {
+    pub bundle: OCABundle,
+    pub warnings: Vec<BuildWarning>,
+}
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum BuildWarning {
+    /// Character encoding which is not a name from the IANA Character Sets registry.
+    NonIanaCharacterEncoding {
+        attribute_name: String,
+        encoding: String,
+        line: usize,
+    },
+    /// Overlay referring to an attribute which isn't declared in the capture base.
+    DanglingOverlayReference {
+        overlay_type: OverlayType,
+        attribute_name: String,
+        line: usize,
+    },
+}
+
+impl fmt::Display for BuildWarning {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            BuildWarning::NonIanaCharacterEncoding {
+                attribute_name,
+                encoding,
+                line,
+            } => write!(
+                f,
+                "Line {line}: character encoding \"{encoding}\" of attribute \"{attribute_name}\" is not an IANA character set name"
+            ),
+            BuildWarning::DanglingOverlayReference {
+                overlay_type,
+                attribute_name,
+                line,
+            } => write!(
+                f,
+                "Line {line}: {overlay_type} overlay refers to undeclared attribute \"{attribute_name}\""
+            ),
+        }
+    }
+}
+
+#[derive(Debug, thiserror::Error)]
+pub enum BuildError {
+    #[error(transparent)]
+    Ocafile(#[from] OcafileError),
+    #[error(
+        "Build warnings treated as errors: {}",
+        .0.iter().map(|w| w.to_string()).collect::<Vec<_>>().join(", ")
+    )]
+    WarningsAsErrors(Vec<BuildWarning>),
+    #[error("Schema \"{name}\": {source}")]
+    Schema { name: String, source: OcafileError },
+    #[error("Line {line}: SCHEMA directive without a name")]
+    MissingSchemaName { line: usize },
+    #[error("Line {line}: command outside of a SCHEMA block")]
+    CommandOutsideSchema { line: usize },
+    #[error("{overlay_type} overlay requires OCA specification {minimum_version} or later")]
+    UnsupportedOverlayForVersion {
+        overlay_type: OverlayType,
+        minimum_version: OcaSpecVersion,
+    },
+}
+
+/// Builds an OCA Bundle from OCAFile like [`build_from_ocafile`](crate::build_from_ocafile),
+/// additionally checking the OCAFile directives.
+///
+/// Findings are returned as [`BuildResult::warnings`], or as
+/// [`BuildError::WarningsAsErrors`] if [`BuildOptions::strict`] is set. Most overlays
+/// referring to undeclared attributes and most non-IANA character encodings make the
+/// build itself fail, in which case the findings are returned as
+/// [`BuildError::WarningsAsErrors`] too, as they point to the offending lines.
+///
+/// Overlays which aren't part of [`BuildOptions::oca_spec_version`] are reported as
+/// [`BuildError::UnsupportedOverlayForVersion`].
+pub fn build_from_ocafile_with_options(
+    ocafile: String,
+    options: &BuildOptions,
+) -> Result<BuildResult, BuildError> {
+    // Parsing errors are left to be reported by the build itself.
+    let warnings = match parse_from_string(ocafile.clone()) {
+        Ok(ParsedOCAAst::SemanticsAst(ast)) => {
+            check_spec_version(&ast, options.oca_spec_version)?;
+            let mut warnings = check_character_encodings(&ast);
+            warnings.extend(check_overlay_references(&ast));
+            warnings
+        }
+        _ => vec![],
+    };
+
+    if options.strict && !warnings.is_empty() {
+        return Err(BuildError::WarningsAsErrors(warnings));
+    }
+
+    let bundle = match build_from_ocafile(ocafile) {
+        Ok(bundle) => bundle,
+        Err(err) => {
+            if warnings.is_empty() {
+                return Err(err.into());
+            }
+            return Err(BuildError::WarningsAsErrors(warnings));
+        }
+    };
+
+    Ok(BuildResult { bundle, warnings })
+}
+
+fn check_spec_version(ast: &OCAAst, version: OcaSpecVersion) -> Result<(), BuildError> {
+    for command in &ast.commands {
+        if let ObjectKind::Overlay(overlay_type, _) = &command.object_kind {
+            let minimum_version = OcaSpecVersion::introducing(overlay_type);
+            if minimum_version > version {
+                return Err(BuildError::UnsupportedOverlayForVersion {
+                    overlay_type: overlay_type.clone(),
+                    minimum_version,
+                });
+            }
+        }
+    }
+
+    Ok(())
+}
+
+fn check_character_encodings(ast: &OCAAst) -> Vec<BuildWarning> {
+    let mut warnings = vec![];
+
+    for (i, command) in ast.commands.iter().enumerate() {
+        let ObjectKind::Overlay(OverlayType::CharacterEncoding(_), content) = &command.object_kind
+        else {
+            continue;
+        };
+        let line = ast
+            .commands_meta
+            .get(&i)
+            .map(|meta| meta.line_number)
+            .unwrap_or_default();
+
+        for (attribute_name, value) in content.attributes.iter().flatten() {
+            if let NestedValue::Value(encoding) = value {
+                if !is_iana_character_set(encoding) {
+                    warnings.push(BuildWarning::NonIanaCharacterEncoding {
+                        attribute_name: attribute_name.clone(),
+                        encoding: encoding.clone(),
+                        line,
+                    });
+                }
+            }
+        }
+    }
+
+    warnings
+}
+
+fn check_overlay_references(ast: &OCAAst) -> Vec<BuildWarning> {
+    let mut declared = HashSet::new();
+    let mut warnings = vec![];
+
+    for (i, command) in ast.commands.iter().enumerate() {
+        match (&command.kind, &command.object_kind) {
+            // Attributes of the base bundle are unknown here.
+            (CommandType::From, _) => return vec![],
+            (CommandType::Add, ObjectKind::CaptureBase(content)) => {
+                declared.extend(content.attributes.iter().flat_map(|attrs| attrs.keys()));
+            }
+            (CommandType::Remove, ObjectKind::CaptureBase(content)) => {
+                for name in content.attributes.iter().flat_map(|attrs| attrs.keys()) {
+                    declared.remove(&name);
+                }
+            }
+            (CommandType::Add, ObjectKind::Overlay(overlay_type, content)) => {
+                let line = ast
+                    .commands_meta
+                    .get(&i)
+                    .map(|meta| meta.line_number)
+                    .unwrap_or_default();
+
+                for attribute_name in content.attributes.iter().flat_map(|attrs| attrs.keys()) {
+                    if !declared.contains(&attribute_name) {
+                        warnings.push(BuildWarning::DanglingOverlayReference {
+                            overlay_type: overlay_type.clone(),
+                            attribute_name: attribute_name.clone(),
+                            line,
+                        });
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    warnings
+}
+
+fn is_iana_character_set(name: &str) -> bool {
+    IANA_CHARACTER_SETS
+        .iter()
+        .any(|charset| charset.eq_ignore_ascii_case(name))
+}
+
+/// OCAFile directives parsed without building the bundle, see [`parse_ocafile_ast`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct OcaFileAst {
+    pub directives: Vec<Directive>,
+    /// Values of `-- key=value` meta comments.
+    pub meta: HashMap<String, String>,
+}
+
+/// Single OCAFile directive, e.g. `ADD LABEL en ATTRS name="Name"`.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Directive {
+    pub kind: CommandType,
+    /// Object the directive applies to along with its parsed attributes and properties.
+    pub object_kind: ObjectKind,
+    /// 1-based line number of the directive in the OCAFile.
+    pub line: usize,
+    pub raw_line: String,
+}
+
+/// Parses OCAFile into its directives without building the bundle, e.g. for linting,
+/// formatting or language servers.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::build::{parse_ocafile_ast, CommandType};
+///
+/// let ast = parse_ocafile_ast("ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"").unwrap();
+/// assert_eq!(ast.directives.len(), 2);
+/// assert_eq!(ast.directives[1].kind, CommandType::Add);
+/// assert_eq!(ast.directives[1].line, 2);
+/// ```
+pub fn parse_ocafile_ast(ocafile: &str) -> Result<OcaFileAst, OcaSdkError> {
+    let ast = match parse_from_string(ocafile.to_string()) {
+        Ok(ParsedOCAAst::SemanticsAst(ast)) => ast,
+        Ok(ParsedOCAAst::TransformationAst(_)) => {
+            return Err(OcaSdkError::Ocafile(
+                "transformation OCAFile is not supported".to_string(),
+            ))
+        }
+        Err(err) => return Err(OcaSdkError::Ocafile(err.to_string())),
+    };
+
+    let directives = ast
+        .commands
+        .into_iter()
+        .enumerate()
+        .map(|(i, command)| {
+            let meta = ast.commands_meta.get(&i);
+            Directive {
+                kind: command.kind,
+                object_kind: command.object_kind,
+                line: meta.map(|meta| meta.line_number).unwrap_or_default(),
+                raw_line: meta.map(|meta| meta.raw_line.clone()).unwrap_or_default(),
+            }
+        })
+        .collect();
+
+    Ok(OcaFileAst {
+        directives,
+        meta: ast.meta,
+    })
+}
+
+/// Builds OCA Bundles from OCAFile defining several schemas.
+///
+/// Each `SCHEMA <name>` directive starts a block of commands built into a separate bundle,
+/// returned in the order of the blocks. OCAFile without `SCHEMA` directives is built into a
+/// single bundle like with [`build_from_ocafile`](crate::build_from_ocafile). Line numbers
+/// reported in build errors refer to the whole OCAFile.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::build::build_schemas_from_ocafile;
+///
+/// let ocafile = "SCHEMA person\nADD ATTRIBUTE name=Text\n\
+///                SCHEMA address\nADD ATTRIBUTE city=Text";
+/// let bundles = build_schemas_from_ocafile(ocafile.to_string()).unwrap();
+/// assert_eq!(bundles.len(), 2);
+/// ```
+pub fn build_schemas_from_ocafile(ocafile: String) -> Result<Vec<OCABundle>, BuildError> {
+    let mut blocks: Vec<(String, String)> = vec![];
+    for (i, line) in ocafile.lines().enumerate() {
+        let mut words = line.split_whitespace();
+        let is_schema = words
+            .next()
+            .is_some_and(|word| word.eq_ignore_ascii_case("SCHEMA"));
+        if is_schema {
+            let name = words
+                .next()
+                .ok_or(BuildError::MissingSchemaName { line: i + 1 })?;
+            // Preceding lines are kept empty, so that line numbers refer to the whole file.
+            blocks.push((name.to_string(), "\n".repeat(i + 1)));
+            continue;
+        }
+
+        match blocks.last_mut() {
+            Some((_, block)) => {
+                block.push_str(line);
+                block.push('\n');
+            }
+            None if is_command(line) => {
+                return Err(BuildError::CommandOutsideSchema { line: i + 1 });
+            }
+            None => {}
+        }
+    }
+
+    if blocks.is_empty() {
+        return Ok(vec![build_from_ocafile(ocafile)?]);
+    }
+
+    blocks
+        .into_iter()
+        .map(|(name, block)| {
+            build_from_ocafile(block).map_err(|source| BuildError::Schema { name, source })
+        })
+        .collect()
+}
+
+/// Returns `true` if the line is neither empty nor a comment.
+fn is_command(line: &str) -> bool {
+    let line = line.trim();
+    !line.is_empty() && !line.starts_with('#') && !line.starts_with("--")
+}
+
+/// Options of [`parse_oca_bundle_to_ocafile_opts`].
+#[derive(Debug, Clone, Default)]
+pub struct ParseToOcafileOptions {
+    /// Prepends the bundle and capture base SAIDs as comments, for reference. Off by default
+    /// to keep the OCAFile readable and easy to diff.
+    pub include_saids: bool,
+}
+
+/// Generates OCAFile from the bundle like
+/// [`parse_oca_bundle_to_ocafile`](crate::parse_oca_bundle_to_ocafile), which is the
+/// equivalent of this function with the default options.
+pub fn parse_oca_bundle_to_ocafile_opts(
+    bundle: &OCABundle,
+    opts: ParseToOcafileOptions,
+) -> Result<String, OcaSdkError> {
+    let ocafile = parse_oca_bundle_to_ocafile(bundle);
+    if !opts.include_saids {
+        return Ok(ocafile);
+    }
+
+    let mut header = String::new();
+    if let Some(said) = &bundle.said {
+        header.push_str(&format!("# Bundle SAID: {}\n", said));
+    }
+    if let Some(said) = &bundle.capture_base.said {
+        header.push_str(&format!("# Capture base SAID: {}\n", said));
+    }
+
+    Ok(header + &ocafile)
+}
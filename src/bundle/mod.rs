@@ -0,0 +1,898 @@
+use crate::{attribute_type_name, data_validator::pattern, OcaSdkError, OverlayConflict, WithInfo};
+use chrono::{DateTime, Utc};
+use oca_ast_semantics::ast::{AttributeType, NestedAttrType, OverlayType};
+use oca_bundle_semantics::state::{
+    attribute::Attribute,
+    entry_codes::EntryCodes,
+    oca::{capture_base::CaptureBase, overlay, DynOverlay, OCABox, OCABundle},
+};
+use serde_json::{json, Map, Value};
+use std::collections::{BTreeSet, HashSet};
+use std::fmt;
+use std::mem::{discriminant, Discriminant};
+
+mod registry;
+pub use registry::{bundle_ancestors, BundleRegistry, BundleResolver};
+
+/// Appends the overlays of `other` to `base` and recomputes the bundle SAID.
+///
+/// Both bundles have to share the same capture base. Overlays already present in `base`
+/// (same SAID) are skipped. An overlay of the same type and language as one already in
+/// `base` is reported as [`OcaSdkError::OverlayConflict`] rather than overwriting it.
+///
+/// `std::ops::Add` can't be implemented for `OCABundle` outside of `oca-bundle-semantics`,
+/// hence the plain function.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build_from_ocafile, bundle::compose};
+///
+/// let english = build_from_ocafile(
+///     "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+/// ).unwrap();
+/// let french = build_from_ocafile(
+///     "ADD ATTRIBUTE name=Text\nADD LABEL fr ATTRS name=\"Nom\"".to_string(),
+/// ).unwrap();
+///
+/// let bundle = compose(english, french).unwrap();
+/// assert_eq!(bundle.overlays.len(), 2);
+/// ```
+pub fn compose(base: OCABundle, other: OCABundle) -> Result<OCABundle, OcaSdkError> {
+    if base.capture_base.said != other.capture_base.said {
+        return Err(OcaSdkError::CaptureBaseMismatch {
+            expected: said_to_string(&base.capture_base.said),
+            found: said_to_string(&other.capture_base.said),
+        });
+    }
+
+    let mut composed = base;
+    for overlay in other.overlays {
+        if composed.overlays.iter().any(|o| o.said() == overlay.said()) {
+            continue;
+        }
+        if composed
+            .overlays
+            .iter()
+            .any(|o| overlay_key(o) == overlay_key(&overlay))
+        {
+            return Err(OcaSdkError::OverlayConflict {
+                overlay_type: overlay.overlay_type().clone(),
+                language: overlay.language().map(|l| l.to_639_3().to_string()),
+            });
+        }
+        composed.overlays.push(overlay);
+    }
+    composed.fill_said();
+
+    Ok(composed)
+}
+
+/// Returns a copy of the bundle with overlays in languages other than `lang` removed.
+///
+/// Language-neutral overlays are kept and the bundle SAID is recomputed. `lang` is an
+/// ISO 639-3 (e.g. `eng`) or ISO 639-1 (e.g. `en`) code.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::LanguageNotFound)` if the bundle has no overlay in `lang`.
+pub fn strip_overlays_for_language(
+    bundle: &OCABundle,
+    lang: &str,
+) -> Result<OCABundle, OcaSdkError> {
+    let mut stripped = bundle.clone();
+    stripped.overlays.retain(|o| match o.language() {
+        Some(l) => l.to_639_3() == lang || l.to_639_1() == Some(lang),
+        None => true,
+    });
+
+    if !stripped.overlays.iter().any(|o| o.language().is_some()) {
+        return Err(OcaSdkError::LanguageNotFound(lang.to_string()));
+    }
+    stripped.fill_said();
+
+    Ok(stripped)
+}
+
+/// Validates the structural integrity of the bundle, complementing the SAID checks of
+/// [`validate_semantics`](crate::validate_semantics).
+///
+/// Reports overlays conflicting with each other, i.e. present more than once for the same
+/// type and language, invalid conformance overlays, see [`validate_conformance_overlay`], and
+/// invalid regular expressions in format overlays, see [`validate_format_overlay`].
+pub fn validate_structure(bundle: &OCABundle) -> Result<(), Vec<String>> {
+    let mut errors: Vec<String> = overlay_conflicts(&bundle.overlays)
+        .iter()
+        .map(|conflict| conflict.to_string())
+        .collect();
+    if let Err(conformance_errors) = validate_conformance_overlay(bundle) {
+        errors.extend(conformance_errors);
+    }
+    if let Err(format_errors) = validate_format_overlay(bundle) {
+        errors.extend(format_errors);
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that the conformance overlay refers only to capture base attributes, and that each
+/// conformance is either `M` (mandatory) or `O` (optional).
+pub fn validate_conformance_overlay(bundle: &OCABundle) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+    for conformance in bundle
+        .overlays
+        .iter()
+        .filter_map(|o| o.as_any().downcast_ref::<overlay::Conformance>())
+    {
+        let mut attributes: Vec<(&String, &String)> =
+            conformance.attribute_conformance.iter().collect();
+        attributes.sort();
+
+        for (name, value) in attributes {
+            if !bundle.capture_base.attributes.contains_key(name) {
+                errors.push(format!(
+                    "Conformance overlay refers to attribute \"{}\" missing from the capture base",
+                    name
+                ));
+            }
+            if value != "M" && value != "O" {
+                errors.push(format!(
+                    "Conformance of attribute \"{}\" is \"{}\", expected \"M\" or \"O\"",
+                    name, value
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that the formats the format overlay assigns to text attributes as regular
+/// expressions compile.
+pub fn validate_format_overlay(bundle: &OCABundle) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+    for format in bundle
+        .overlays
+        .iter()
+        .filter_map(|o| o.as_any().downcast_ref::<overlay::Format>())
+    {
+        let mut attributes: Vec<(&String, &String)> = format.attribute_formats.iter().collect();
+        attributes.sort();
+
+        for (name, format) in attributes {
+            let is_text = matches!(
+                bundle.capture_base.attributes.get(name),
+                Some(NestedAttrType::Value(AttributeType::Text))
+            );
+            if !is_text || !pattern::is_pattern(format) {
+                continue;
+            }
+            if let Err(e) = pattern::compile(format) {
+                errors.push(format!(
+                    "Format of attribute \"{}\" is not a valid regular expression: {}",
+                    name, e
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+/// Checks that the bundle contains overlays of all the required types, e.g. before publishing
+/// it to a registry. Overlays of language-specific types (label, information, meta and entry)
+/// are required in each of the required languages, given as ISO 639-1 or ISO 639-3 codes.
+///
+/// # Errors
+/// * Returns `Err` listing each missing overlay type and language combination.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build_from_ocafile, bundle::validate_bundle_completeness, OverlayType};
+///
+/// let bundle = build_from_ocafile(
+///     "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+/// ).unwrap();
+/// let label = OverlayType::Label("1.1".to_string());
+///
+/// assert!(validate_bundle_completeness(&bundle, &["en"], &[label.clone()]).is_ok());
+/// assert!(validate_bundle_completeness(&bundle, &["en", "fr"], &[label]).is_err());
+/// ```
+pub fn validate_bundle_completeness(
+    bundle: &OCABundle,
+    required_languages: &[&str],
+    required_overlay_types: &[OverlayType],
+) -> Result<(), Vec<String>> {
+    let mut errors = vec![];
+    for overlay_type in required_overlay_types {
+        let overlays = bundle
+            .overlays
+            .iter()
+            .filter(|o| discriminant(o.overlay_type()) == discriminant(overlay_type))
+            .collect::<Vec<_>>();
+
+        if !is_language_specific(overlay_type) {
+            if overlays.is_empty() {
+                errors.push(format!("Missing {} overlay", overlay_type));
+            }
+            continue;
+        }
+        for language in required_languages {
+            let present = overlays.iter().any(|o| {
+                o.language()
+                    .is_some_and(|l| l.to_639_3() == *language || l.to_639_1() == Some(*language))
+            });
+            if !present {
+                errors.push(format!(
+                    "Missing {} overlay in {} language",
+                    overlay_type, language
+                ));
+            }
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(())
+    } else {
+        Err(errors)
+    }
+}
+
+fn is_language_specific(overlay_type: &OverlayType) -> bool {
+    matches!(
+        overlay_type,
+        OverlayType::Label(_)
+            | OverlayType::Information(_)
+            | OverlayType::Meta(_)
+            | OverlayType::Entry(_)
+    )
+}
+
+/// Computes the SAID of the bundle the same way as
+/// [`build_from_ocafile`](crate::build_from_ocafile) does, i.e. a Blake3-256 digest of the JSON
+/// serialization, regardless of the SAID the bundle currently holds. The capture base and
+/// overlay SAIDs are taken as they are.
+///
+/// Comparing the result with `bundle.said` verifies the bundle integrity.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build_from_ocafile, bundle::compute_bundle_said};
+///
+/// let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+/// assert_eq!(
+///     compute_bundle_said(&bundle).unwrap(),
+///     bundle.said.unwrap().to_string()
+/// );
+/// ```
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::SkeletonBundle)` for a bundle created with [`skeleton_bundle`].
+/// * Returns `Err(OcaSdkError::SaidComputation)` if the SAID couldn't be computed.
+pub fn compute_bundle_said(bundle: &OCABundle) -> Result<String, OcaSdkError> {
+    ensure_not_skeleton(bundle)?;
+    let mut bundle = bundle.clone();
+    bundle.said = None;
+    bundle.fill_said();
+
+    bundle
+        .said
+        .map(|said| said.to_string())
+        .ok_or(OcaSdkError::SaidComputation)
+}
+
+/// Creates a placeholder bundle for a known capture base SAID, e.g. taken from a DID
+/// document, to stand in for the bundle until it's fetched. The skeleton has no attributes,
+/// no overlays and no bundle SAID.
+///
+/// Functions which need the bundle content, e.g. [`compute_bundle_said`] or
+/// [`ToJSON::get_json_bundle`](crate::ToJSON::get_json_bundle), return
+/// `Err(OcaSdkError::SkeletonBundle)` for it, see [`is_skeleton`].
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::bundle::{is_skeleton, skeleton_bundle};
+///
+/// let bundle = skeleton_bundle("EGQbKoJjLEcAfjkg3iW9tpwmnmidqxActG-dB0onEvqw").unwrap();
+/// assert!(is_skeleton(&bundle));
+/// ```
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::InvalidSaid)` if `capture_base_said` isn't a valid SAID.
+pub fn skeleton_bundle(capture_base_said: &str) -> Result<OCABundle, OcaSdkError> {
+    let said =
+        capture_base_said
+            .parse()
+            .map_err(|e: said::error::Error| OcaSdkError::InvalidSaid {
+                said: capture_base_said.to_string(),
+                message: e.to_string(),
+            })?;
+
+    let mut capture_base = CaptureBase::new();
+    capture_base.said = Some(said);
+    Ok(OCABundle {
+        said: None,
+        capture_base,
+        overlays: vec![],
+    })
+}
+
+/// Tells whether the bundle is a placeholder created with [`skeleton_bundle`].
+pub fn is_skeleton(bundle: &OCABundle) -> bool {
+    bundle.said.is_none()
+        && bundle.capture_base.said.is_some()
+        && bundle.capture_base.attributes.is_empty()
+        && bundle.overlays.is_empty()
+}
+
+/// Returns `Err(OcaSdkError::SkeletonBundle)` for bundles created with [`skeleton_bundle`].
+pub(crate) fn ensure_not_skeleton(bundle: &OCABundle) -> Result<(), OcaSdkError> {
+    match &bundle.capture_base.said {
+        Some(said) if is_skeleton(bundle) => Err(OcaSdkError::SkeletonBundle(said.to_string())),
+        _ => Ok(()),
+    }
+}
+
+/// Outcome of [`is_bundle_compatible_with`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct CompatibilityResult {
+    /// The second bundle accepts all data the first one accepts.
+    pub forward_compatible: bool,
+    /// The first bundle accepts all data the second one accepts.
+    pub backward_compatible: bool,
+    /// Changes from the first to the second bundle rejecting data valid for the first one.
+    pub breaking_changes: Vec<String>,
+}
+
+/// Checks whether data valid for bundle `a` is also valid for bundle `b`, and vice versa.
+///
+/// Attribute types, conformance and entry codes are compared. Attributes unknown to a bundle
+/// are ignored by the data validation, hence removing an attribute isn't a breaking change,
+/// while adding a mandatory one is.
+pub fn is_bundle_compatible_with(a: &OCABundle, b: &OCABundle) -> CompatibilityResult {
+    let a = declared_oca_box(a.clone());
+    let b = declared_oca_box(b.clone());
+
+    let breaking_changes = rejected_changes(&a, &b);
+    CompatibilityResult {
+        forward_compatible: breaking_changes.is_empty(),
+        backward_compatible: rejected_changes(&b, &a).is_empty(),
+        breaking_changes,
+    }
+}
+
+/// Checks whether the `small` bundle is a subset of the `large` one, e.g. that the schema a
+/// consumer expects is provided.
+///
+/// Every attribute of `small` has to exist in `large` with the same type, and the same
+/// conformance or a widened one, that is mandatory in `small` and optional in `large`.
+pub fn is_subset_of(small: &OCABundle, large: &OCABundle) -> bool {
+    let small = declared_oca_box(small.clone());
+    let large = declared_oca_box(large.clone());
+
+    small.attributes.values().all(|small_attr| {
+        let Some(large_attr) = large.attributes.get(&small_attr.name) else {
+            return false;
+        };
+        let is_mandatory = |attr: &Attribute| attr.conformance.as_deref() == Some("M");
+
+        small_attr.attribute_type == large_attr.attribute_type
+            && (is_mandatory(small_attr) || !is_mandatory(large_attr))
+    })
+}
+
+/// Outcome of [`compare_schemas`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct SchemaCompatibilityReport {
+    /// The capture bases match and there are no overlay differences.
+    pub structurally_identical: bool,
+    /// Both capture bases declare the same attributes, of the same types.
+    pub capture_base_match: bool,
+    pub overlay_differences: Vec<OverlayDiff>,
+}
+
+/// Difference between overlays of two bundles. Overlays are matched by type and scope, that
+/// is the language, the link target bundle or the frame id.
+#[derive(Debug, Clone, PartialEq)]
+pub enum OverlayDiff {
+    /// Overlay present only in the first bundle.
+    Removed {
+        overlay_type: OverlayType,
+        scope: Option<String>,
+    },
+    /// Overlay present only in the second bundle.
+    Added {
+        overlay_type: OverlayType,
+        scope: Option<String>,
+    },
+    /// Overlay present in both bundles with different content.
+    Changed {
+        overlay_type: OverlayType,
+        scope: Option<String>,
+    },
+}
+
+/// Compares the bundles semantically, regardless of their SAIDs, which differ e.g. due to
+/// the overlays order or the capture base SAID overlays are bound to.
+pub fn compare_schemas(a: &OCABundle, b: &OCABundle) -> SchemaCompatibilityReport {
+    let flagged =
+        |bundle: &OCABundle| BTreeSet::from_iter(bundle.capture_base.flagged_attributes.clone());
+    let capture_base_match = a.capture_base.attributes.len() == b.capture_base.attributes.len()
+        && a.capture_base
+            .attributes
+            .iter()
+            .all(|(name, attr_type)| b.capture_base.attributes.get(name) == Some(attr_type))
+        && flagged(a) == flagged(b);
+
+    let mut overlay_differences = vec![];
+    for overlay in &a.overlays {
+        let key = overlay_key(overlay);
+        let (overlay_type, scope) = (overlay.overlay_type().clone(), key.1.clone());
+        match b.overlays.iter().find(|o| overlay_key(o) == key) {
+            None => overlay_differences.push(OverlayDiff::Removed {
+                overlay_type,
+                scope,
+            }),
+            Some(other) if overlay_content(overlay) != overlay_content(other) => {
+                overlay_differences.push(OverlayDiff::Changed {
+                    overlay_type,
+                    scope,
+                })
+            }
+            Some(_) => {}
+        }
+    }
+    for overlay in &b.overlays {
+        let key = overlay_key(overlay);
+        if !a.overlays.iter().any(|o| overlay_key(o) == key) {
+            overlay_differences.push(OverlayDiff::Added {
+                overlay_type: overlay.overlay_type().clone(),
+                scope: key.1,
+            });
+        }
+    }
+
+    SchemaCompatibilityReport {
+        structurally_identical: capture_base_match && overlay_differences.is_empty(),
+        capture_base_match,
+        overlay_differences,
+    }
+}
+
+/// Changes between two versions of a bundle, see [`diff`].
+#[derive(Debug, Clone)]
+pub struct OCABundleDiff {
+    /// Attributes present only in the second bundle.
+    pub added: Vec<Attribute>,
+    /// Attributes present only in the first bundle.
+    pub removed: Vec<Attribute>,
+    /// Attributes present in both bundles with a different type or conformance.
+    pub modified: Vec<AttributeChange>,
+    pub overlay_changes: Vec<OverlayDiff>,
+}
+
+/// Type or conformance change of an attribute, see [`OCABundleDiff::modified`].
+#[derive(Debug, Clone, PartialEq)]
+pub struct AttributeChange {
+    pub name: String,
+    pub old_type: Option<NestedAttrType>,
+    pub new_type: Option<NestedAttrType>,
+    pub old_conformance: Option<String>,
+    pub new_conformance: Option<String>,
+}
+
+/// Lists the attributes added, removed or modified from bundle `a` to bundle `b`, along with
+/// the overlay differences reported by [`compare_schemas`], e.g. for migration tooling or
+/// audit logs. Attributes are listed in alphabetical order.
+pub fn diff(a: &OCABundle, b: &OCABundle) -> OCABundleDiff {
+    let overlay_changes = compare_schemas(a, b).overlay_differences;
+    let a = declared_oca_box(a.clone());
+    let b = declared_oca_box(b.clone());
+
+    let sorted = |oca_box: &OCABox, other: &OCABox| {
+        let mut attributes = oca_box
+            .attributes
+            .values()
+            .filter(|attr| !other.attributes.contains_key(&attr.name))
+            .cloned()
+            .collect::<Vec<_>>();
+        attributes.sort_by(|x, y| x.name.cmp(&y.name));
+        attributes
+    };
+
+    let mut names: Vec<&String> = a.attributes.keys().collect();
+    names.sort();
+    let modified = names
+        .into_iter()
+        .filter_map(|name| {
+            let (old, new) = (&a.attributes[name], b.attributes.get(name)?);
+            (old.attribute_type != new.attribute_type || old.conformance != new.conformance).then(
+                || AttributeChange {
+                    name: name.clone(),
+                    old_type: old.attribute_type.clone(),
+                    new_type: new.attribute_type.clone(),
+                    old_conformance: old.conformance.clone(),
+                    new_conformance: new.conformance.clone(),
+                },
+            )
+        })
+        .collect();
+
+    OCABundleDiff {
+        added: sorted(&b, &a),
+        removed: sorted(&a, &b),
+        modified,
+        overlay_changes,
+    }
+}
+
+/// Returns the overlay serialization without its SAID and the capture base SAID.
+fn overlay_content(overlay: &DynOverlay) -> Option<serde_json::Value> {
+    let mut content = serde_json::to_value(overlay).ok()?;
+    if let Some(object) = content.as_object_mut() {
+        object.remove("d");
+        object.remove("capture_base");
+    }
+    Some(content)
+}
+
+/// Returns the changes from `from` to `to` because of which `to` rejects data accepted by
+/// `from`.
+fn rejected_changes(from: &OCABox, to: &OCABox) -> Vec<String> {
+    let mut names: Vec<&String> = to.attributes.keys().collect();
+    names.sort();
+
+    let mut changes = vec![];
+    for name in names {
+        let to_attr = &to.attributes[name];
+        let to_required = to_attr.conformance.as_deref() == Some("M");
+        let Some(from_attr) = from.attributes.get(name) else {
+            if to_required {
+                changes.push(format!("Attribute \"{}\" was added as mandatory", name));
+            }
+            continue;
+        };
+
+        if from_attr.attribute_type != to_attr.attribute_type {
+            let type_name = |attr: &Attribute| {
+                attr.attribute_type
+                    .as_ref()
+                    .map(attribute_type_name)
+                    .unwrap_or_default()
+            };
+            changes.push(format!(
+                "Attribute \"{}\" type changed from {} to {}",
+                name,
+                type_name(from_attr),
+                type_name(to_attr)
+            ));
+        }
+        if to_required && from_attr.conformance.as_deref() != Some("M") {
+            changes.push(format!("Attribute \"{}\" became mandatory", name));
+        }
+
+        if let Some(to_codes) = entry_codes(to_attr) {
+            let restricted = match entry_codes(from_attr) {
+                Some(from_codes) => !from_codes.is_subset(&to_codes),
+                None => true,
+            };
+            if restricted {
+                changes.push(format!(
+                    "Attribute \"{}\" entry codes were restricted",
+                    name
+                ));
+            }
+        }
+    }
+
+    changes
+}
+
+/// Returns the entry codes allowed for the attribute, or `None` if any value is allowed.
+/// A SAID of an external entry codes set is treated as a single code.
+fn entry_codes(attribute: &Attribute) -> Option<BTreeSet<String>> {
+    match attribute.entry_codes.as_ref()? {
+        EntryCodes::Sai(said) => Some(BTreeSet::from([said.clone()])),
+        EntryCodes::Array(codes) => Some(codes.iter().cloned().collect()),
+        EntryCodes::Object(groups) => Some(groups.values().flatten().cloned().collect()),
+    }
+}
+
+/// Returns the entry codes of the `attr_name` attribute, with the codes of all groups for
+/// grouped entry codes, or no codes if the attribute has none.
+///
+/// Entry codes given by SAID refer to a bundle holding the code set, which is fetched with
+/// `resolver`; the entry codes of its attribute of the same name are returned.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::AttributeNotFound)` if either bundle has no `attr_name`
+///   attribute.
+/// * Returns `Err(OcaSdkError::MissingReference)` if the referenced bundle can't be resolved
+///   or refers to another code set in turn.
+pub fn resolve_entry_codes(
+    bundle: &OCABundle,
+    attr_name: &str,
+    resolver: &dyn BundleResolver,
+) -> Result<Vec<String>, OcaSdkError> {
+    let attribute_entry_codes = |bundle: OCABundle| {
+        declared_oca_box(bundle)
+            .attributes
+            .get(attr_name)
+            .map(|attr| attr.entry_codes.clone())
+            .ok_or_else(|| OcaSdkError::AttributeNotFound(attr_name.to_string()))
+    };
+
+    let entry_codes = match attribute_entry_codes(bundle.clone())? {
+        Some(EntryCodes::Sai(said)) => {
+            let code_set = resolver
+                .resolve(&said)
+                .ok_or_else(|| OcaSdkError::MissingReference { said: said.clone() })?;
+            match attribute_entry_codes(code_set)? {
+                Some(EntryCodes::Sai(_)) => return Err(OcaSdkError::MissingReference { said }),
+                entry_codes => entry_codes,
+            }
+        }
+        entry_codes => entry_codes,
+    };
+
+    Ok(match entry_codes {
+        Some(EntryCodes::Array(codes)) => codes,
+        Some(EntryCodes::Object(groups)) => groups.into_values().flatten().collect(),
+        Some(EntryCodes::Sai(_)) | None => vec![],
+    })
+}
+
+/// Brief `Debug` representation of a bundle, see [`summary`].
+pub struct BundleSummary<'a>(&'a OCABundle);
+
+impl fmt::Debug for BundleSummary<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("OCABundle")
+            .field("said", &said_to_string(&self.0.said))
+            .field("attribute_count", &self.0.capture_base.attributes.len())
+            .field("overlay_count", &self.0.overlays.len())
+            .finish()
+    }
+}
+
+/// Returns a `Debug` representation of the bundle listing only its SAID and the number of
+/// attributes and overlays, e.g. for logs and test failures, where the derived `Debug` of
+/// `OCABundle` dumps the whole bundle.
+///
+/// `OCABundle` already derives `Debug` in `oca-bundle-semantics`, hence the wrapper.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build_from_ocafile, bundle::summary};
+///
+/// let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+/// let summary = format!("{:?}", summary(&bundle));
+/// assert!(summary.starts_with("OCABundle { said: \""));
+/// assert!(summary.ends_with("attribute_count: 1, overlay_count: 0 }"));
+/// ```
+pub fn summary(bundle: &OCABundle) -> BundleSummary<'_> {
+    BundleSummary(bundle)
+}
+
+/// Provenance metadata of a bundle, see [`audit_trail`].
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct AuditTrail {
+    pub author: Option<String>,
+    pub created_by_tool: Option<String>,
+    pub created_at: Option<DateTime<Utc>>,
+    pub signed_by: Option<String>,
+}
+
+/// Extracts the provenance metadata some bundles embed in the meta overlays under the
+/// `author`, `created_by_tool`, `created_at` (an RFC 3339 timestamp) and `signed_by` keys.
+///
+/// Each key is taken from the first language, in alphabetical order, defining it. A
+/// `created_at` which isn't a valid timestamp is skipped.
+pub fn audit_trail(bundle: &OCABundle) -> AuditTrail {
+    let info = bundle.info();
+    let meta = |key: &str| {
+        info.languages_having_meta_key(key)
+            .first()
+            .and_then(|lang| info.meta[*lang].get(key))
+            .cloned()
+    };
+
+    AuditTrail {
+        author: meta("author"),
+        created_by_tool: meta("created_by_tool"),
+        created_at: meta("created_at")
+            .and_then(|created_at| DateTime::parse_from_rfc3339(&created_at).ok())
+            .map(|created_at| created_at.with_timezone(&Utc)),
+        signed_by: meta("signed_by"),
+    }
+}
+
+/// Flattens the bundle into a JSON object keyed by attribute name, for debugging. Each
+/// attribute is described by its `type`, whether it's `required`, its `label` in the `lang`
+/// language (ISO 639-1 or ISO 639-3 code), its `entry_codes` and its `format`, with `null`
+/// for the missing ones.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build_from_ocafile, bundle::to_flat_json};
+///
+/// let bundle = build_from_ocafile(
+///     "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+/// ).unwrap();
+/// let flat = to_flat_json(&bundle, "en").unwrap();
+/// assert_eq!(flat["name"]["label"], "Name");
+/// ```
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::SkeletonBundle)` for a bundle created with [`skeleton_bundle`].
+/// * Returns `Err(OcaSdkError::LanguageNotFound)` if `lang` isn't a language of the bundle
+///   overlays.
+pub fn to_flat_json(bundle: &OCABundle, lang: &str) -> Result<Value, OcaSdkError> {
+    ensure_not_skeleton(bundle)?;
+    let info = bundle.info();
+    if !bundle.overlays.iter().any(|o| {
+        o.language()
+            .is_some_and(|l| l.to_639_3() == lang || l.to_639_1() == Some(lang))
+    }) {
+        return Err(OcaSdkError::LanguageNotFound(lang.to_string()));
+    }
+
+    let mut attributes = info.attributes().collect::<Vec<_>>();
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let flat: Map<String, Value> = attributes
+        .into_iter()
+        .map(|attr| {
+            let label = attr
+                .labels
+                .iter()
+                .flatten()
+                .find(|(l, _)| l.to_639_3() == lang || l.to_639_1() == Some(lang))
+                .map(|(_, label)| label.clone());
+            let entry_codes = attr.entry_codes.as_ref().map(|codes| match codes {
+                EntryCodes::Sai(said) => json!(said),
+                EntryCodes::Array(codes) => json!(codes),
+                EntryCodes::Object(groups) => json!(groups),
+            });
+            let description = json!({
+                "type": attr.attribute_type.as_ref().map(attribute_type_name),
+                "required": attr.conformance.as_deref() == Some("M"),
+                "label": label,
+                "entry_codes": entry_codes,
+                "format": attr.format,
+            });
+            (attr.name.clone(), description)
+        })
+        .collect();
+
+    Ok(Value::Object(flat))
+}
+
+/// Converts the bundle into an `OCABox`, the mutable representation of a bundle. The bundle
+/// can be regenerated with [`OCABox::generate_bundle`].
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Bundle)` if overlays refer to, or the capture base flags,
+///   attributes missing from the capture base, which an `OCABox` can't hold.
+pub fn into_oca_box(bundle: OCABundle) -> Result<OCABox, OcaSdkError> {
+    let undeclared = undeclared_attributes(&bundle);
+    if !undeclared.is_empty() {
+        return Err(OcaSdkError::Bundle(format!(
+            "attributes missing from the capture base: {}",
+            undeclared.into_iter().collect::<Vec<_>>().join(", ")
+        )));
+    }
+
+    Ok(OCABox::from(bundle))
+}
+
+/// Returns the attributes overlays refer to, or the capture base flags, which are missing
+/// from the capture base. `OCABox::from` panics on them.
+fn undeclared_attributes(bundle: &OCABundle) -> BTreeSet<String> {
+    let declared = &bundle.capture_base.attributes;
+    bundle
+        .overlays
+        .iter()
+        .flat_map(|overlay| overlay.attributes())
+        .chain(&bundle.capture_base.flagged_attributes)
+        .filter(|name| !declared.contains_key(*name))
+        .cloned()
+        .collect()
+}
+
+/// Converts the bundle into an `OCABox` like [`into_oca_box`], without failing on attributes
+/// missing from the capture base: they are declared for the conversion and left without a
+/// type afterwards. Returns their names along with the `OCABox`.
+pub(crate) fn into_oca_box_lenient(mut bundle: OCABundle) -> (OCABox, BTreeSet<String>) {
+    let undeclared = undeclared_attributes(&bundle);
+    for name in &undeclared {
+        bundle
+            .capture_base
+            .attributes
+            .insert(name.clone(), NestedAttrType::Null);
+    }
+
+    let mut oca_box = OCABox::from(bundle);
+    for name in &undeclared {
+        if let Some(attribute) = oca_box.attributes.get_mut(name) {
+            attribute.attribute_type = None;
+        }
+    }
+
+    (oca_box, undeclared)
+}
+
+/// Converts the bundle into an `OCABox` of the capture base attributes only, leaving out the
+/// ones missing from the capture base, e.g. to compare bundles coming from outside the crate.
+pub(crate) fn declared_oca_box(bundle: OCABundle) -> OCABox {
+    let (mut oca_box, undeclared) = into_oca_box_lenient(bundle);
+    oca_box
+        .attributes
+        .retain(|name, _| !undeclared.contains(name));
+    oca_box
+}
+
+/// Generates a bundle from the `OCABox`, computing the capture base, overlay and bundle
+/// SAIDs. The reverse of [`into_oca_box`].
+///
+/// `TryFrom<OCABox>` can't be implemented for `OCABundle` outside of `oca-bundle-semantics`,
+/// hence the plain function.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::SaidComputation)` if the bundle SAID couldn't be computed.
+pub fn from_oca_box(mut oca_box: OCABox) -> Result<OCABundle, OcaSdkError> {
+    let bundle = oca_box.generate_bundle();
+    match bundle.said {
+        Some(_) => Ok(bundle),
+        None => Err(OcaSdkError::SaidComputation),
+    }
+}
+
+/// Returns overlays present more than once for the same type and language. Each conflict is
+/// reported once, regardless of the number of duplicates.
+pub(crate) fn overlay_conflicts(overlays: &[DynOverlay]) -> Vec<OverlayConflict> {
+    let mut seen = HashSet::new();
+    let mut reported = HashSet::new();
+
+    overlays
+        .iter()
+        .filter_map(|overlay| {
+            let key = overlay_key(overlay);
+            if seen.insert(key.clone()) || !reported.insert(key) {
+                return None;
+            }
+            Some(OverlayConflict {
+                overlay_type: overlay.overlay_type().clone(),
+                language: overlay.language().map(|l| l.to_639_3().to_string()),
+            })
+        })
+        .collect()
+}
+
+/// Identifies the slot an overlay occupies within a bundle. Links and framings may appear
+/// several times, once per target bundle and frame respectively.
+pub(crate) fn overlay_key(overlay: &DynOverlay) -> (Discriminant<OverlayType>, Option<String>) {
+    let scope = if let Some(link) = overlay.as_any().downcast_ref::<overlay::Link>() {
+        Some(link.target_bundle.clone())
+    } else if let Some(framing) = overlay.as_any().downcast_ref::<overlay::AttributeFraming>() {
+        framing.metadata.get("frame_id").cloned()
+    } else {
+        overlay.language().map(|l| l.to_639_3().to_string())
+    };
+
+    (discriminant(overlay.overlay_type()), scope)
+}
+
+pub(crate) fn said_to_string<T: ToString>(said: &Option<T>) -> String {
+    said.as_ref().map(|s| s.to_string()).unwrap_or_default()
+}
@@ -0,0 +1,114 @@
+use super::said_to_string;
+use log::warn;
+use oca_bundle_semantics::state::oca::{overlay, OCABundle};
+use std::collections::{HashMap, HashSet};
+
+/// Collection of bundles looked up by their SAIDs, e.g. to resolve link overlays.
+#[derive(Default)]
+pub struct BundleRegistry {
+    bundles: HashMap<String, OCABundle>,
+}
+
+impl BundleRegistry {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Adds the bundle under its SAID, replacing a bundle already registered with it.
+    pub fn insert(&mut self, bundle: OCABundle) {
+        self.bundles.insert(said_to_string(&bundle.said), bundle);
+    }
+
+    /// Returns the bundle with the given SAID, if registered.
+    pub fn get(&self, said: &str) -> Option<&OCABundle> {
+        self.bundles.get(said)
+    }
+
+    pub fn len(&self) -> usize {
+        self.bundles.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.bundles.is_empty()
+    }
+}
+
+/// Source of bundles referenced by SAID, e.g. a registry client or an in-memory collection.
+pub trait BundleResolver {
+    /// Returns the bundle with the given SAID, or `None` if it can't be found.
+    fn resolve(&self, said: &str) -> Option<OCABundle>;
+}
+
+impl BundleResolver for BundleRegistry {
+    fn resolve(&self, said: &str) -> Option<OCABundle> {
+        self.get(said).cloned()
+    }
+}
+
+impl BundleResolver for HashMap<String, OCABundle> {
+    fn resolve(&self, said: &str) -> Option<OCABundle> {
+        self.get(said).cloned()
+    }
+}
+
+impl FromIterator<OCABundle> for BundleRegistry {
+    fn from_iter<I: IntoIterator<Item = OCABundle>>(iter: I) -> Self {
+        let mut registry = Self::new();
+        for bundle in iter {
+            registry.insert(bundle);
+        }
+        registry
+    }
+}
+
+/// Returns the bundles the bundle is derived from, following the target bundles of its link
+/// overlays and of theirs, depth first.
+///
+/// Targets missing from the registry are skipped, and bundles reachable through several
+/// links are returned once. A link back to a bundle on the path being followed is a cycle,
+/// which is logged as a warning and not followed.
+pub fn bundle_ancestors<'a>(
+    registry: &'a BundleRegistry,
+    bundle: &OCABundle,
+) -> Vec<&'a OCABundle> {
+    let said = said_to_string(&bundle.said);
+    let mut visited = HashSet::from([said.clone()]);
+    let mut path = vec![said];
+    let mut ancestors = vec![];
+    collect_ancestors(registry, bundle, &mut visited, &mut path, &mut ancestors);
+    ancestors
+}
+
+fn collect_ancestors<'a>(
+    registry: &'a BundleRegistry,
+    bundle: &OCABundle,
+    visited: &mut HashSet<String>,
+    path: &mut Vec<String>,
+    ancestors: &mut Vec<&'a OCABundle>,
+) {
+    let targets = bundle
+        .overlays
+        .iter()
+        .filter_map(|o| o.as_any().downcast_ref::<overlay::Link>())
+        .map(|link| &link.target_bundle);
+
+    for target in targets {
+        if path.contains(target) {
+            warn!(
+                "Link cycle detected: bundle {} links to bundle {} on the path to it",
+                said_to_string(&bundle.said),
+                target
+            );
+            continue;
+        }
+        if !visited.insert(target.clone()) {
+            continue;
+        }
+        if let Some(ancestor) = registry.get(target) {
+            ancestors.push(ancestor);
+            path.push(target.clone());
+            collect_ancestors(registry, ancestor, visited, path, ancestors);
+            path.pop();
+        }
+    }
+}
@@ -0,0 +1,19 @@
+//! Conversions between OCA Bundles and JSON Schema, in both directions.
+//!
+//! # Examples
+//! ```
+//! use oca_sdk_rs::{
+//!     build_from_ocafile,
+//!     conversion::{build_from_json_schema, to_json_schema},
+//!     WithInfo,
+//! };
+//!
+//! let bundle = build_from_ocafile(
+//!     "ADD ATTRIBUTE name=Text\nADD CONFORMANCE ATTRS name=M".to_string()
+//! ).unwrap();
+//! let schema = to_json_schema(&bundle);
+//! let converted = build_from_json_schema(&schema.to_string()).unwrap();
+//! assert_eq!(converted.info().attribute("name").unwrap().conformance.as_deref(), Some("M"));
+//! ```
+pub use crate::build::build_from_json_schema;
+pub use crate::export::json_schema::to_json_schema;
@@ -0,0 +1,62 @@
+use super::{
+    validate_attributes, validation_box, DataValidationError, DataValidationStatus,
+    ValidationOptions,
+};
+use crate::OcaSdkError;
+use oca_bundle_semantics::state::{attribute::Attribute, oca::OCABundle};
+use serde_json::Value;
+
+/// Custom attribute validation which may perform I/O, e.g. look a value up in a remote
+/// registry or a database.
+#[async_trait::async_trait]
+pub trait AsyncCustomValidator: Send + Sync {
+    /// Validates the value of the attribute, returning error messages if it's invalid.
+    async fn validate(&self, attr: &Attribute, value: &Value) -> Vec<String>;
+}
+
+/// Validates the data against the `OCABundle` like [`validate_data`](super::validate_data),
+/// and then runs the custom `validators` on each attribute value present in the data.
+///
+/// # Errors
+/// * Returns `Err` if the data can't be validated like in
+///   [`validate_data`](super::validate_data).
+pub async fn validate_data_async(
+    oca: &OCABundle,
+    data: &Value,
+    validators: Vec<Box<dyn AsyncCustomValidator>>,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let mut errors: Vec<DataValidationError> =
+        validate_attributes(&oca_box, data, &ValidationOptions::default(), None)?
+            .into_iter()
+            .flatten()
+            .map(|violation| violation.error)
+            .collect();
+
+    let mut attributes: Vec<&Attribute> = oca_box.attributes.values().collect();
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    for attribute in attributes {
+        let Some(value) = data.get(&attribute.name) else {
+            continue;
+        };
+        for validator in &validators {
+            errors.extend(
+                validator
+                    .validate(attribute, value)
+                    .await
+                    .into_iter()
+                    .map(|message| DataValidationError::Custom {
+                        attribute: attribute.name.clone(),
+                        message,
+                    }),
+            );
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(DataValidationStatus::Valid)
+    } else {
+        Ok(DataValidationStatus::Invalid(errors))
+    }
+}
@@ -0,0 +1,23 @@
+//! Syntax check of email addresses.
+
+#[cfg(not(feature = "strict-email"))]
+lazy_static::lazy_static! {
+    /// Valid email address as defined by the HTML standard, a practical subset of RFC 5322
+    /// addresses: no comments, quoted local parts or IP address literals.
+    static ref EMAIL_ADDRESS: regex::Regex = regex::Regex::new(
+        r"^[A-Za-z0-9.!#$%&'*+/=?^_`{|}~-]+@[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?(?:\.[A-Za-z0-9](?:[A-Za-z0-9-]{0,61}[A-Za-z0-9])?)*$"
+    )
+    .unwrap();
+}
+
+/// Returns `true` if the value is a syntactically valid email address.
+#[cfg(not(feature = "strict-email"))]
+pub(crate) fn is_email_address(value: &str) -> bool {
+    EMAIL_ADDRESS.is_match(value)
+}
+
+/// Returns `true` if the value is an email address valid according to RFC 5322.
+#[cfg(feature = "strict-email")]
+pub(crate) fn is_email_address(value: &str) -> bool {
+    email_address::EmailAddress::is_valid(value)
+}
@@ -1,10 +1,34 @@
-use oca_ast_semantics::ast::{AttributeType, NestedAttrType};
+use crate::{bundle, validate_semantics, OcaSdkError};
+use base64::engine::general_purpose::{STANDARD, URL_SAFE, URL_SAFE_NO_PAD};
+use base64::Engine;
+use chrono::{DateTime, NaiveDate, NaiveDateTime, NaiveTime};
+use log::warn;
+use oca_ast_semantics::ast::{AttributeType, NestedAttrType, OverlayType, RefValue};
+use oca_bundle_semantics::controller::load_oca;
 use oca_bundle_semantics::state::{
     attribute::Attribute,
     entry_codes::EntryCodes,
     oca::{OCABox, OCABundle},
+    validator::SemanticValidationStatus,
 };
-use serde_json::Value;
+use serde_json::{Map, Number, Value};
+use std::collections::HashMap;
+use std::fmt;
+use std::fs;
+use std::io::Read;
+use std::mem::discriminant;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+mod email;
+pub(crate) mod iso3166;
+pub(crate) mod iso4217;
+pub(crate) mod pattern;
+
+#[cfg(feature = "tokio")]
+mod asynchronous;
+#[cfg(feature = "tokio")]
+pub use asynchronous::{validate_data_async, AsyncCustomValidator};
 
 /// Represents the validation status of the data.
 ///
@@ -13,11 +37,424 @@ use serde_json::Value;
 ///
 /// # Variants
 /// * `Valid` - Indicates that the data is valid and meets all validation criteria.
-/// * `Invalid(Vec<String>)` - Indicates that the data is invalid. Contains a vector
-///   of error messages describing the validation issues.
+/// * `Invalid(Vec<DataValidationError>)` - Indicates that the data is invalid. Contains a
+///   vector of errors describing the validation issues.
 pub enum DataValidationStatus {
     Valid,
-    Invalid(Vec<String>),
+    Invalid(Vec<DataValidationError>),
+}
+
+/// Data validation error. The [`Display`](fmt::Display) implementation gives a
+/// human-readable message and [`DataValidationError::path`] the location of the invalid value.
+#[derive(Debug, Clone, PartialEq)]
+pub enum DataValidationError {
+    /// The data is not a JSON object.
+    NotAnObject,
+    /// Mandatory attribute value is missing.
+    MandatoryMissing { attribute: String },
+    /// Value is not of the attribute type. `got` is the value as JSON.
+    TypeMismatch {
+        attribute: String,
+        expected: AttributeType,
+        got: String,
+    },
+    /// Array element is not of the element type of the attribute. `got` is the element as
+    /// JSON.
+    ElementTypeMismatch {
+        attribute: String,
+        index: usize,
+        expected: AttributeType,
+        got: String,
+    },
+    /// Value of an array attribute is not an array. `got` is the value as JSON.
+    NotAnArray { attribute: String, got: String },
+    /// Value of a code-like attribute has leading or trailing whitespace, see
+    /// [`ValidationOptions::forbid_surrounding_whitespace`].
+    SurroundingWhitespace { attribute: String, value: String },
+    /// Number has a fractional part, while the format overlay declares the attribute as an
+    /// integer, e.g. `{"integer": true}`.
+    NotAnInteger { attribute: String, value: String },
+    /// Number has more decimal places than the format overlay allows.
+    PrecisionExceeded {
+        attribute: String,
+        value: String,
+        precision: usize,
+    },
+    /// Value of a `Binary` attribute is neither base64 nor base64url encoded.
+    InvalidBase64 { attribute: String },
+    /// Decoded value of a `Binary` attribute is larger than the format overlay allows, see
+    /// [`ValidationOptions::enforce_binary_size`].
+    BinaryTooLarge {
+        attribute: String,
+        size: usize,
+        max_size: usize,
+    },
+    /// Number is lower than the minimum of the [`NumericRange`] declared in the format overlay.
+    BelowMinimum {
+        attribute: String,
+        value: String,
+        minimum: f64,
+    },
+    /// Number is greater than the maximum of the [`NumericRange`] declared in the format
+    /// overlay.
+    AboveMaximum {
+        attribute: String,
+        value: String,
+        maximum: f64,
+    },
+    /// Value doesn't match the format declared in the format overlay. `format` describes the
+    /// expected value, e.g. "a valid UUID".
+    InvalidFormat {
+        attribute: String,
+        value: String,
+        format: String,
+    },
+    /// Text doesn't match the regular expression assigned by the format overlay.
+    FormatMismatch {
+        attribute: String,
+        value: String,
+        format: String,
+    },
+    /// Value is not one of the entry codes of the attribute.
+    EntryCodeViolation {
+        attribute: String,
+        value: String,
+        allowed: Vec<String>,
+    },
+    /// Error found in the object of a reference attribute, or in the `index` element of an
+    /// array of references, see [`validate_data_with_refs`].
+    Nested {
+        attribute: String,
+        index: Option<usize>,
+        error: Box<DataValidationError>,
+    },
+    /// Error reported by a custom validator.
+    Custom { attribute: String, message: String },
+    /// Data holds a key not matching any attribute of the bundle, see
+    /// [`ValidationOptions::strict_unknown`].
+    UnexpectedAttribute { attribute: String },
+    /// Number of further errors found before the validation stopped, see
+    /// [`ValidationOptions::max_errors`]. The count is a lower bound, as attributes left
+    /// unvalidated may hold more.
+    Suppressed { count: usize },
+}
+
+impl DataValidationError {
+    /// Returns the name of the attribute whose value is invalid, empty for
+    /// [`DataValidationError::NotAnObject`] and [`DataValidationError::Suppressed`].
+    pub fn attribute(&self) -> &str {
+        match self {
+            DataValidationError::NotAnObject | DataValidationError::Suppressed { .. } => "",
+            DataValidationError::MandatoryMissing { attribute }
+            | DataValidationError::TypeMismatch { attribute, .. }
+            | DataValidationError::ElementTypeMismatch { attribute, .. }
+            | DataValidationError::NotAnArray { attribute, .. }
+            | DataValidationError::SurroundingWhitespace { attribute, .. }
+            | DataValidationError::NotAnInteger { attribute, .. }
+            | DataValidationError::PrecisionExceeded { attribute, .. }
+            | DataValidationError::InvalidBase64 { attribute }
+            | DataValidationError::BinaryTooLarge { attribute, .. }
+            | DataValidationError::BelowMinimum { attribute, .. }
+            | DataValidationError::AboveMaximum { attribute, .. }
+            | DataValidationError::InvalidFormat { attribute, .. }
+            | DataValidationError::FormatMismatch { attribute, .. }
+            | DataValidationError::EntryCodeViolation { attribute, .. }
+            | DataValidationError::Nested { attribute, .. }
+            | DataValidationError::Custom { attribute, .. }
+            | DataValidationError::UnexpectedAttribute { attribute } => attribute,
+        }
+    }
+
+    /// Returns the location of the invalid value in the data as an RFC 6901 JSON Pointer,
+    /// e.g. `/address/postal_code` or `/scores/3`, so that a form can highlight the failing
+    /// field. The pointer is empty for [`DataValidationError::NotAnObject`] and
+    /// [`DataValidationError::Suppressed`].
+    pub fn path(&self) -> String {
+        let attribute = self.attribute().replace('~', "~0").replace('/', "~1");
+        match self {
+            DataValidationError::NotAnObject | DataValidationError::Suppressed { .. } => {
+                String::new()
+            }
+            DataValidationError::ElementTypeMismatch { index, .. } => {
+                format!("/{}/{}", attribute, index)
+            }
+            DataValidationError::Nested {
+                index: Some(index),
+                error,
+                ..
+            } => format!("/{}/{}{}", attribute, index, error.path()),
+            DataValidationError::Nested { error, .. } => {
+                format!("/{}{}", attribute, error.path())
+            }
+            _ => format!("/{}", attribute),
+        }
+    }
+}
+
+impl fmt::Display for DataValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            DataValidationError::NotAnObject => write!(f, "Data is not an object"),
+            DataValidationError::MandatoryMissing { attribute } => {
+                write!(f, "Attribute \"{}\" value is mandatory", attribute)
+            }
+            DataValidationError::TypeMismatch {
+                attribute,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Attribute \"{}\" value ({}) is not {}{}",
+                attribute,
+                got,
+                type_description(expected),
+                boolean_hint(expected, got)
+            ),
+            DataValidationError::ElementTypeMismatch {
+                attribute,
+                index,
+                expected,
+                got,
+            } => write!(
+                f,
+                "Attribute \"{}\" element at index {} ({}) is not {}{}",
+                attribute,
+                index,
+                got,
+                type_description(expected),
+                boolean_hint(expected, got)
+            ),
+            DataValidationError::NotAnArray { attribute, got } => {
+                write!(
+                    f,
+                    "Attribute \"{}\" value ({}) is not an array",
+                    attribute, got
+                )
+            }
+            DataValidationError::SurroundingWhitespace { attribute, value } => write!(
+                f,
+                "Attribute \"{}\" value ({}) has leading/trailing whitespace",
+                attribute, value
+            ),
+            DataValidationError::NotAnInteger { attribute, value } => write!(
+                f,
+                "Attribute \"{}\" value ({}) is not an integer",
+                attribute, value
+            ),
+            DataValidationError::PrecisionExceeded {
+                attribute,
+                value,
+                precision,
+            } => write!(
+                f,
+                "Attribute \"{}\" value ({}) exceeds maximum precision of {} decimal places",
+                attribute, value, precision
+            ),
+            DataValidationError::InvalidBase64 { attribute } => {
+                write!(f, "Attribute \"{}\" value is not valid base64", attribute)
+            }
+            DataValidationError::BinaryTooLarge {
+                attribute,
+                size,
+                max_size,
+            } => write!(
+                f,
+                "Attribute \"{}\" value of {} bytes exceeds maximum size of {} bytes",
+                attribute, size, max_size
+            ),
+            DataValidationError::BelowMinimum {
+                attribute,
+                value,
+                minimum,
+            } => write!(
+                f,
+                "Attribute \"{}\" value ({}) is below minimum {}",
+                attribute, value, minimum
+            ),
+            DataValidationError::AboveMaximum {
+                attribute,
+                value,
+                maximum,
+            } => write!(
+                f,
+                "Attribute \"{}\" value ({}) exceeds maximum {}",
+                attribute, value, maximum
+            ),
+            DataValidationError::InvalidFormat {
+                attribute,
+                value,
+                format,
+            } => write!(
+                f,
+                "Attribute \"{}\" value ({}) is not {}",
+                attribute, value, format
+            ),
+            DataValidationError::FormatMismatch {
+                attribute,
+                value,
+                format,
+            } => write!(
+                f,
+                "Attribute \"{}\" value ({}) does not match format \"{}\"",
+                attribute, value, format
+            ),
+            DataValidationError::EntryCodeViolation {
+                attribute, value, ..
+            } => write!(
+                f,
+                "Attribute \"{}\" value ({}) is not in entry codes",
+                attribute, value
+            ),
+            DataValidationError::Nested {
+                attribute,
+                index: Some(index),
+                error,
+            } => write!(f, "{}[{}]: {}", attribute, index, error),
+            DataValidationError::Nested {
+                attribute, error, ..
+            } => write!(f, "{}: {}", attribute, error),
+            DataValidationError::Custom { message, .. } => write!(f, "{}", message),
+            DataValidationError::UnexpectedAttribute { attribute } => write!(
+                f,
+                "Unexpected attribute \"{}\" not present in schema",
+                attribute
+            ),
+            DataValidationError::Suppressed { count } => write!(
+                f,
+                "... (at least {} more {} suppressed)",
+                count,
+                if *count == 1 { "error" } else { "errors" }
+            ),
+        }
+    }
+}
+
+/// Options tuning the data validation performed by [`validate_data_with_options`].
+#[derive(Debug, Clone, Default)]
+pub struct ValidationOptions {
+    /// Reports text values with leading or trailing whitespace for code-like attributes,
+    /// that is attributes constrained by entry codes or a format (e.g. identifiers or country
+    /// codes), where `" US "` would otherwise fail to match `"US"`.
+    pub forbid_surrounding_whitespace: bool,
+    /// Maximum number of nested object and array levels of the data, the top-level object
+    /// included. Deeper data, e.g. produced from circular structures, is rejected before
+    /// validation.
+    pub max_depth: Option<usize>,
+    /// Reports optional attributes absent from the data as [`ValidationResult::warnings`],
+    /// for records expected to list every attribute, with explicit nulls for missing values.
+    pub require_all_attributes: bool,
+    /// Formats accepted for `DateTime` values. `None` accepts RFC 3339 timestamps and dates,
+    /// i.e. `[DateTimeFormat::Rfc3339, DateTimeFormat::Date]`. Attributes with a format hint
+    /// in the format overlay, e.g. `YYYY-MM-DD`, are validated against it instead.
+    pub datetime_formats: Option<Vec<DateTimeFormat>>,
+    /// Reports keys of the data not matching any attribute of the bundle, e.g. misspelled
+    /// attribute names, which are otherwise ignored. Objects of reference attributes are
+    /// checked against the referenced bundles as well.
+    pub strict_unknown: bool,
+    /// Maximum number of errors reported. Validation stops at the attribute exceeding the
+    /// cap, and the errors beyond it are replaced with a single
+    /// [`DataValidationError::Suppressed`], e.g. to keep responses of a validation endpoint
+    /// bounded in size. `None` reports all errors.
+    ///
+    /// As the remaining attributes aren't validated, the suppressed count is a lower bound,
+    /// and unknown keys aren't reported even if [`ValidationOptions::strict_unknown`] is set.
+    pub max_errors: Option<usize>,
+    /// Measures how long validation of each attribute takes, reported as
+    /// [`ValidationReport::timings`], e.g. to find slow checks of large bundles.
+    pub record_timing: bool,
+    /// Reports values of `Binary` attributes larger, once decoded, than the number of bytes
+    /// declared in the format overlay as JSON, e.g. `{"max_size": 65536}`.
+    pub enforce_binary_size: bool,
+}
+
+/// Inclusive bounds of numeric attribute values, declared in the format overlay as JSON, e.g.
+/// `{"min": 0, "max": 120}`. Either bound may be left out.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct NumericRange {
+    pub min: Option<f64>,
+    pub max: Option<f64>,
+}
+
+impl NumericRange {
+    /// Parses the bounds from the format of an attribute, returning `None` if it declares
+    /// neither of them.
+    pub fn from_format(format: &str) -> Option<Self> {
+        let format: Value = serde_json::from_str(format).ok()?;
+        let range = NumericRange {
+            min: format.get("min").and_then(Value::as_f64),
+            max: format.get("max").and_then(Value::as_f64),
+        };
+        (range.min.is_some() || range.max.is_some()).then_some(range)
+    }
+}
+
+/// Format of `DateTime` attribute values, see [`ValidationOptions::datetime_formats`].
+#[derive(Debug, Clone, PartialEq)]
+pub enum DateTimeFormat {
+    /// RFC 3339 timestamp, e.g. `2024-05-01T12:30:00Z`.
+    Rfc3339,
+    /// Date without time, `YYYY-MM-DD`.
+    Date,
+    /// Legacy format given as a `chrono` format string, e.g. `%d/%m/%Y %H:%M`, with or
+    /// without time and time zone.
+    Custom(String),
+}
+
+impl DateTimeFormat {
+    /// Converts a format overlay hint built of `YYYY`, `MM`, `DD`, `HH` (24-hour clock), `hh`
+    /// (12-hour clock), `mm`, `ss` and `A` (AM/PM) placeholders, e.g. `YYYY-MM-DDTHH:mm:ss` or
+    /// `hh:mm A`, to a `chrono` format.
+    pub(crate) fn from_hint(hint: &str) -> Self {
+        let format = [
+            ("YYYY", "%Y"),
+            ("MM", "%m"),
+            ("DD", "%d"),
+            ("hh", "%I"),
+            ("HH", "%H"),
+            ("mm", "%M"),
+            ("ss", "%S"),
+            ("A", "%p"),
+        ]
+        .iter()
+        .fold(
+            hint.replace('%', "%%"),
+            |format, (placeholder, specifier)| format.replace(placeholder, specifier),
+        );
+        DateTimeFormat::Custom(format)
+    }
+
+    fn parses(&self, value: &str) -> bool {
+        match self {
+            DateTimeFormat::Rfc3339 => DateTime::parse_from_rfc3339(value).is_ok(),
+            DateTimeFormat::Date => NaiveDate::parse_from_str(value, "%Y-%m-%d").is_ok(),
+            DateTimeFormat::Custom(format) => {
+                DateTime::parse_from_str(value, format).is_ok()
+                    || NaiveDateTime::parse_from_str(value, format).is_ok()
+                    || NaiveDate::parse_from_str(value, format).is_ok()
+                    || NaiveTime::parse_from_str(value, format).is_ok()
+            }
+        }
+    }
+}
+
+/// Returns a hint for booleans passed as strings, e.g. by form encoders, given the JSON of
+/// the value.
+fn boolean_hint(expected: &AttributeType, got: &str) -> String {
+    match (expected, got) {
+        (AttributeType::Boolean, "\"true\"" | "\"false\"") => format!(
+            ". Did you mean to pass a boolean (true/false) rather than the string {}?",
+            got
+        ),
+        _ => String::new(),
+    }
+}
+
+fn type_description(attribute_type: &AttributeType) -> &'static str {
+    match attribute_type {
+        AttributeType::Numeric => "a number",
+        AttributeType::Boolean => "a boolean",
+        AttributeType::Text | AttributeType::DateTime | AttributeType::Binary => "a string",
+    }
 }
 
 /// Validates the provided data against the schema defined in the `OCABundle`.
@@ -29,49 +466,1068 @@ pub enum DataValidationStatus {
 /// # Arguments
 /// * `oca` - A reference to an `OCABundle` that contains the schema for validation.
 /// * `data` - A reference to a `serde_json::Value` representing the data to be validated.
-///   The `data` must be a JSON object.
+///   The `data` is expected to be a JSON object.
 ///
 /// # Returns
 /// * `Ok(DataValidationStatus)` - Indicates whether the data is valid or invalid,
 ///   along with any associated error messages.
-/// * `Err(String)` - Indicates that an error occurred during validation, such as
-///   failure to parse the input data.
+/// * `Err(OcaSdkError)` - Indicates that an error occurred during validation.
 ///
 /// # Errors
-/// * Returns `Err` if the provided `data` cannot be parsed as a JSON object.
+/// * Returns `Err(OcaSdkError::SkeletonBundle)` if the bundle is a
+///   [`skeleton_bundle`](crate::bundle::skeleton_bundle) placeholder, which has no attributes
+///   to validate the data against.
+/// * Returns `Err(OcaSdkError::MaxDepthExceeded)` if the data exceeds
+///   [`ValidationOptions::max_depth`].
 /// * Returns `Ok(DataValidationStatus::Invalid)` if validation fails, with a
-///   vector of detailed error messages.
+///   vector of detailed error messages. Data which is not a JSON object is invalid as well.
+///
+pub fn validate_data(oca: &OCABundle, data: &Value) -> Result<DataValidationStatus, OcaSdkError> {
+    validate_data_with_options(oca, data, &ValidationOptions::default())
+}
+
+/// Validates the attributes present in the data like [`validate_field`], e.g. for intermediate
+/// submissions of a form filled in step by step. Attributes missing from the data, or `null`,
+/// aren't checked, so that mandatory ones may be left for later.
+///
+/// With `strict` set, keys of the data not matching any attribute are reported, like with
+/// [`ValidationOptions::strict_unknown`].
 ///
-pub fn validate_data(oca: &OCABundle, data: &Value) -> Result<DataValidationStatus, String> {
+/// # Errors
+/// * Returns `Err` if the data can't be validated like in [`validate_data`].
+pub fn validate_data_partial(
+    oca: &OCABundle,
+    data: &Value,
+    strict: bool,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let Some(object) = data.as_object() else {
+        return Ok(DataValidationStatus::Invalid(vec![
+            DataValidationError::NotAnObject,
+        ]));
+    };
+    let options = ValidationOptions::default();
+
     let mut errors = vec![];
+    for attribute in oca_box.attributes.values() {
+        if let Some(value) = object.get(&attribute.name).filter(|v| !v.is_null()) {
+            errors.extend(validate_attribute(attribute, Some(value), &options, None)?);
+        }
+    }
+    if strict {
+        errors.extend(unexpected_attributes(&oca_box, data));
+    }
+
+    Ok(status(errors))
+}
+
+/// Validates the provided data against the `OCABundle` like [`validate_data`], applying
+/// additional checks enabled in `options`.
+pub fn validate_data_with_options(
+    oca: &OCABundle,
+    data: &Value,
+    options: &ValidationOptions,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    DataValidator::with_options(oca, options.clone())?.validate(data)
+}
+
+/// Validator of data against an `OCABundle`, prepared once, i.e. with the attributes, entry
+/// codes, formats and conformance extracted from the bundle, and reusable for any number of
+/// records. [`validate_data`] prepares the bundle on every call instead.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{
+///     build_from_ocafile,
+///     data_validator::{DataValidationStatus, DataValidator},
+/// };
+/// use serde_json::json;
+///
+/// let bundle = build_from_ocafile("ADD ATTRIBUTE age=Numeric".to_string()).unwrap();
+/// let validator = DataValidator::new(&bundle).unwrap();
+/// for age in [json!(18), json!(42)] {
+///     assert!(matches!(
+///         validator.validate(&json!({ "age": age })).unwrap(),
+///         DataValidationStatus::Valid
+///     ));
+/// }
+/// ```
+pub struct DataValidator {
+    oca_box: OCABox,
+    options: ValidationOptions,
+}
+
+impl DataValidator {
+    /// Creates the validator.
+    ///
+    /// # Errors
+    /// * Returns `Err(OcaSdkError::SkeletonBundle)` for a skeleton bundle, like
+    ///   [`validate_data`].
+    pub fn new(oca: &OCABundle) -> Result<Self, OcaSdkError> {
+        Self::with_options(oca, ValidationOptions::default())
+    }
+
+    /// Creates the validator applying additional checks enabled in `options`, like
+    /// [`validate_data_with_options`].
+    ///
+    /// # Errors
+    /// * Returns `Err(OcaSdkError::SkeletonBundle)` for a skeleton bundle, like
+    ///   [`validate_data`].
+    pub fn with_options(oca: &OCABundle, options: ValidationOptions) -> Result<Self, OcaSdkError> {
+        Ok(Self {
+            oca_box: validation_box(oca)?,
+            options,
+        })
+    }
+
+    /// Validates the data like [`validate_data`].
+    ///
+    /// # Errors
+    /// * Returns `Err` if the data can't be validated like in [`validate_data`].
+    pub fn validate(&self, data: &Value) -> Result<DataValidationStatus, OcaSdkError> {
+        validate_with_box(&self.oca_box, data, &self.options)
+    }
+
+    /// Validates the data like [`validate`](Self::validate), along with the time spent on
+    /// each attribute if [`ValidationOptions::record_timing`] is set.
+    ///
+    /// # Errors
+    /// * Returns `Err` if the data can't be validated like in [`validate_data`].
+    pub fn validate_with_report(&self, data: &Value) -> Result<ValidationReport, OcaSdkError> {
+        let mut timings = HashMap::new();
+        let errors = validate_attributes_timed(
+            &self.oca_box,
+            data,
+            &self.options,
+            None,
+            self.options.record_timing.then_some(&mut timings),
+        )?;
+
+        Ok(ValidationReport {
+            status: capped_status(errors.into_iter().flatten(), &self.options),
+            timings,
+        })
+    }
+}
+
+/// Outcome of [`validate_data_with_report`].
+pub struct ValidationReport {
+    pub status: DataValidationStatus,
+    /// Time spent on validating each attribute, by attribute name. Empty unless
+    /// [`ValidationOptions::record_timing`] is set.
+    pub timings: HashMap<String, Duration>,
+}
+
+/// Validates the data like [`validate_data_with_options`], reporting the time spent on each
+/// attribute if [`ValidationOptions::record_timing`] is set.
+///
+/// # Errors
+/// * Returns `Err` if the data can't be validated like in [`validate_data`].
+pub fn validate_data_with_report(
+    oca: &OCABundle,
+    data: &Value,
+    options: &ValidationOptions,
+) -> Result<ValidationReport, OcaSdkError> {
+    DataValidator::with_options(oca, options.clone())?.validate_with_report(data)
+}
+
+/// Validates each of the records against the `OCABundle` like [`validate_data`], preparing
+/// the bundle for validation only once, e.g. for large exports converted to JSON.
+///
+/// Returns the result of each record in the order of `records`.
+pub fn validate_batch<'a>(
+    oca: &OCABundle,
+    records: impl Iterator<Item = &'a Value>,
+) -> Vec<Result<DataValidationStatus, OcaSdkError>> {
+    let validator = DataValidator::new(oca);
+
+    records
+        .map(|record| match &validator {
+            Ok(validator) => validator.validate(record),
+            // `OcaSdkError` isn't `Clone`, so the error is recreated for each record.
+            Err(_) => DataValidator::new(oca)?.validate(record),
+        })
+        .collect()
+}
+
+/// Validates the records like [`validate_batch`], e.g. the elements of a JSON array or the
+/// lines of NDJSON, returning the status of each record in their order.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Record)` with the 0-based index of the first record which can't
+///   be validated, along with the reason.
+pub fn validate_data_many(
+    oca: &OCABundle,
+    records: &[Value],
+) -> Result<Vec<DataValidationStatus>, OcaSdkError> {
+    validate_batch(oca, records.iter())
+        .into_iter()
+        .enumerate()
+        .map(|(index, status)| {
+            status.map_err(|e| OcaSdkError::Record {
+                index,
+                source: Box::new(e),
+            })
+        })
+        .collect()
+}
+
+/// Validates the records like [`validate_batch`], stopping at the first invalid one.
+///
+/// # Returns
+/// * `Ok(None)` - If all records are valid.
+/// * `Ok(Some((index, errors)))` - The 0-based index of the first invalid record along with
+///   its errors.
+///
+/// # Errors
+/// * Returns `Err` if a record can't be validated like in [`validate_data`].
+pub fn validate_batch_fail_fast<'a>(
+    oca: &OCABundle,
+    records: impl Iterator<Item = &'a Value>,
+) -> Result<Option<(usize, Vec<DataValidationError>)>, OcaSdkError> {
+    let validator = DataValidator::new(oca)?;
+
+    for (i, record) in records.enumerate() {
+        if let DataValidationStatus::Invalid(errors) = validator.validate(record)? {
+            return Ok(Some((i, errors)));
+        }
+    }
+
+    Ok(None)
+}
+
+/// Outcome of [`validate_full`].
+pub struct ValidationResult {
+    /// Result of the bundle semantics validation.
+    pub semantic_status: SemanticValidationStatus,
+    /// Result of the data validation.
+    pub data_status: DataValidationStatus,
+    /// Findings which don't make the data invalid.
+    pub warnings: Vec<String>,
+    /// The data after [`normalize_data`].
+    pub normalized: Value,
+}
+
+impl ValidationResult {
+    /// Returns human-readable messages of all semantic and data validation errors.
+    pub fn messages(&self) -> Vec<String> {
+        let mut messages = vec![];
+        if let SemanticValidationStatus::Invalid(errors) = &self.semantic_status {
+            messages.extend(errors.iter().map(|e| e.to_string()));
+        }
+        if let DataValidationStatus::Invalid(errors) = &self.data_status {
+            messages.extend(errors.iter().map(|e| e.to_string()));
+        }
+        messages
+    }
+
+    /// Returns `true` if both the bundle semantics and the data are valid.
+    pub fn is_valid(&self) -> bool {
+        matches!(self.semantic_status, SemanticValidationStatus::Valid)
+            && matches!(self.data_status, DataValidationStatus::Valid)
+    }
+}
+
+/// Validates the bundle semantics and the data, and normalizes the data in a single pass.
+///
+/// This is the entry point for services which need all of the validation outcomes at once,
+/// as the bundle is converted for validation only once.
+///
+/// # Errors
+/// * Returns `Err` if the bundle semantics or the data can't be validated.
+pub fn validate_full(
+    oca: &OCABundle,
+    data: &Value,
+    options: &ValidationOptions,
+) -> Result<ValidationResult, OcaSdkError> {
+    let semantic_status = validate_semantics(oca)?;
+
+    let oca_box = validation_box(oca)?;
+    let data_status = validate_with_box(&oca_box, data, options)?;
+    let normalized = normalize_with_box(&oca_box, data);
+    let warnings = if options.require_all_attributes {
+        absent_optional_attributes(&oca_box, data)
+    } else {
+        vec![]
+    };
+
+    Ok(ValidationResult {
+        semantic_status,
+        data_status,
+        warnings,
+        normalized,
+    })
+}
+
+/// Outcome of [`validate_data_and_explain`].
+pub struct ValidationExplanation {
+    pub status: DataValidationStatus,
+    /// Messages of the errors, in the order of the errors, with attribute names replaced by
+    /// their labels.
+    pub messages: Vec<String>,
+}
+
+/// Validates the data like [`validate_data`], explaining the errors in terms of the attribute
+/// labels in `lang`, e.g. `Age value ("x") is not a number` rather than
+/// `Attribute "age" value ("x") is not a number`, for messages shown to end users.
+///
+/// `lang` is an ISO 639-3 (e.g. `eng`) or ISO 639-1 (e.g. `en`) code. Attributes without a
+/// label in `lang` keep their names.
+///
+/// # Errors
+/// * Returns `Err` if the data can't be validated like in [`validate_data`].
+pub fn validate_data_and_explain(
+    oca: &OCABundle,
+    data: &Value,
+    lang: &str,
+) -> Result<ValidationExplanation, OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let status = validate_with_box(&oca_box, data, &ValidationOptions::default())?;
+    let messages = match &status {
+        DataValidationStatus::Valid => vec![],
+        DataValidationStatus::Invalid(errors) => errors
+            .iter()
+            .map(|error| explain(error, &oca_box, lang))
+            .collect(),
+    };
+
+    Ok(ValidationExplanation { status, messages })
+}
+
+/// Returns the message of the error with the attribute name replaced by its label in `lang`.
+fn explain(error: &DataValidationError, oca_box: &OCABox, lang: &str) -> String {
+    let message = error.to_string();
+    let Some(label) = oca_box
+        .attributes
+        .get(error.attribute())
+        .and_then(|attr| attr.labels.as_ref())
+        .and_then(|labels| {
+            labels
+                .iter()
+                .find(|(l, _)| l.to_639_3() == lang || l.to_639_1() == Some(lang))
+        })
+        .map(|(_, label)| label)
+    else {
+        return message;
+    };
+
+    // Messages of nested errors start with the bare attribute name.
+    let prefix = match error {
+        DataValidationError::Nested { attribute, .. } => attribute.clone(),
+        _ => format!("Attribute \"{}\"", error.attribute()),
+    };
+    match message.strip_prefix(&prefix) {
+        Some(rest) => format!("{}{}", label, rest),
+        None => message,
+    }
+}
+
+/// Reports optional attributes which aren't keys of the data object. Absent mandatory
+/// attributes are validation errors instead.
+fn absent_optional_attributes(oca_box: &OCABox, data: &Value) -> Vec<String> {
+    let Some(object) = data.as_object() else {
+        return vec![];
+    };
+
+    let mut names = oca_box
+        .attributes
+        .values()
+        .filter(|attr| attr.conformance.as_deref() != Some("M"))
+        .filter(|attr| !object.contains_key(&attr.name))
+        .map(|attr| &attr.name)
+        .collect::<Vec<_>>();
+    names.sort();
+
+    names
+        .into_iter()
+        .map(|name| format!("Optional attribute \"{}\" is absent", name))
+        .collect()
+}
+
+/// Reads an OCA Bundle and captured data from files, and validates both of them.
+///
+/// The bundle semantics are validated first, followed by the data validation like in
+/// [`validate_data`].
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Io)` if either file can't be read.
+/// * Returns `Err(OcaSdkError::Parse)` if the bundle or the data can't be parsed.
+/// * Returns `Err(OcaSdkError::InvalidBundle)` if the bundle semantics are invalid.
+pub fn validate_captures_data_file(
+    oca_bundle_path: &Path,
+    data_path: &Path,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    let read = |path: &Path| {
+        fs::read_to_string(path).map_err(|source| OcaSdkError::Io {
+            path: path.to_path_buf(),
+            source,
+        })
+    };
+
+    let oca = load_oca(&mut read(oca_bundle_path)?.as_bytes()).map_err(|e| OcaSdkError::Parse {
+        path: oca_bundle_path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+    let data: Value = serde_json::from_str(&read(data_path)?).map_err(|e| OcaSdkError::Parse {
+        path: data_path.to_path_buf(),
+        message: e.to_string(),
+    })?;
+
+    if let SemanticValidationStatus::Invalid(errors) = validate_semantics(&oca)? {
+        return Err(OcaSdkError::InvalidBundle {
+            path: oca_bundle_path.to_path_buf(),
+            errors: errors.iter().map(|e| e.to_string()).collect(),
+        });
+    }
+
+    validate_data(&oca, &data)
+        .map_err(|e| OcaSdkError::ValidationError(format!("{}: {}", data_path.display(), e)))
+}
+
+/// Completeness of the data expressed as the share of passed attribute validations.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationScore {
+    /// `passed_checks / total_checks`, or `1.0` if there was nothing to check.
+    pub score: f32,
+    /// Number of attribute validations performed.
+    pub total_checks: usize,
+    /// Number of attribute validations which found no errors.
+    pub passed_checks: usize,
+}
+
+/// Validates the data like [`validate_data`] and scores how much of it is valid.
+///
+/// Each bundle attribute counts as one check, passed if no errors were found for it.
+pub fn validate_data_scored(
+    oca: &OCABundle,
+    data: &Value,
+) -> Result<(DataValidationStatus, ValidationScore), OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let errors = validate_attributes(&oca_box, data, &ValidationOptions::default(), None)?;
+
+    let total_checks = errors.len();
+    let passed_checks = errors.iter().filter(|e| e.is_empty()).count();
+    let score = if total_checks == 0 {
+        1.0
+    } else {
+        passed_checks as f32 / total_checks as f32
+    };
+
+    Ok((
+        status(errors.into_iter().flatten()),
+        ValidationScore {
+            score,
+            total_checks,
+            passed_checks,
+        },
+    ))
+}
+
+/// Validates a single attribute value against the `OCABundle`, e.g. when a form field has
+/// changed, without validating the rest of the data.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::AttributeNotFound)` if the bundle has no `attr_name` attribute.
+pub fn validate_attribute_value(
+    oca: &OCABundle,
+    attr_name: &str,
+    value: &Value,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let attribute = attribute_by_name(&oca_box, attr_name)?;
+
+    let errors = validate_attribute(attribute, Some(value), &ValidationOptions::default(), None)?;
+
+    Ok(status(errors))
+}
+
+/// Validates a form field value against the `OCABundle`, checking only the value itself,
+/// that is its type, format and entry codes.
+///
+/// Unlike [`validate_attribute_value`], a `null` value stands for a field not filled in yet
+/// and is valid, as the conformance is left to be checked with [`validate_data`] once the
+/// whole form is submitted.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::AttributeNotFound)` if the bundle has no `attr_name` attribute.
+pub fn validate_field(
+    oca: &OCABundle,
+    attr_name: &str,
+    value: &Value,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    if value.is_null() {
+        attribute_by_name(&validation_box(oca)?, attr_name)?;
+        return Ok(DataValidationStatus::Valid);
+    }
+
+    validate_attribute_value(oca, attr_name, value)
+}
+
+/// Data validation error pointing to the overlay which defines the violated rule.
+#[derive(Debug, Clone, PartialEq)]
+pub struct ValidationError {
+    /// Name of the attribute whose value is invalid.
+    pub attribute: String,
+    /// Human-readable message, the same as reported in [`DataValidationStatus::Invalid`].
+    pub message: String,
+    /// SAID of the overlay whose rule was violated, e.g. the conformance overlay for a missing
+    /// mandatory value. `None` for the capture base rules, that is attribute types.
+    pub overlay_said: Option<String>,
+}
+
+impl fmt::Display for ValidationError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(f, "{}", self.message)
+    }
+}
+
+/// Validates the data like [`validate_data_with_options`], reporting for each error the
+/// overlay whose rule was violated.
+///
+/// # Errors
+/// * Returns `Err` if the data can't be validated, like in [`validate_data_with_options`].
+pub fn validate_data_detailed(
+    oca: &OCABundle,
+    data: &Value,
+    options: &ValidationOptions,
+) -> Result<Vec<ValidationError>, OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let errors = validate_attributes(&oca_box, data, options, None)?;
+
+    Ok(capped(errors.into_iter().flatten(), options)
+        .into_iter()
+        .map(|violation| ValidationError {
+            overlay_said: violation.rule.overlay_type().and_then(|overlay_type| {
+                oca.overlays
+                    .iter()
+                    .find(|o| discriminant(o.overlay_type()) == discriminant(&overlay_type))
+                    .and_then(|o| o.said().as_ref())
+                    .map(|said| said.to_string())
+            }),
+            attribute: violation.error.attribute().to_string(),
+            message: violation.error.to_string(),
+        })
+        .collect())
+}
+
+/// Validates the data against the `OCABundle` like [`validate_data`], additionally validating
+/// objects of reference attributes, and objects in arrays of references, against the
+/// referenced bundles, recursively.
+///
+/// Referenced bundles are looked up in `refs` by their SAIDs (or names, for references by
+/// name), so that they don't have to be fetched. Errors found in nested objects are prefixed
+/// with the name of the referencing attribute, e.g. `address: Attribute "city" value is
+/// mandatory`, followed by the index for array elements, e.g. `items[1]: ...`.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::MissingReference)` if a referenced bundle is missing from
+///   `refs`.
+/// * Returns `Err` if the data can't be validated like in [`validate_data`].
+pub fn validate_data_with_refs(
+    oca: &OCABundle,
+    data: &Value,
+    refs: &HashMap<String, OCABundle>,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    validate_data_with_refs_and_options(oca, data, refs, &ValidationOptions::default())
+}
+
+/// Validates the data like [`validate_data_with_refs`], applying additional checks enabled in
+/// `options` to the referenced objects as well.
+pub fn validate_data_with_refs_and_options(
+    oca: &OCABundle,
+    data: &Value,
+    refs: &HashMap<String, OCABundle>,
+    options: &ValidationOptions,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let errors = validate_attributes(&oca_box, data, options, Some(refs))?;
+
+    Ok(capped_status(errors.into_iter().flatten(), options))
+}
+
+/// Validates CSV records against the `OCABundle`.
+///
+/// The first row holds the headers, i.e. attribute names, and each subsequent row is validated
+/// as a data record like in [`validate_data`]. Cells are converted according to the attribute
+/// type: numbers and booleans are parsed, arrays and references are read as JSON and empty
+/// cells are treated as missing values. Headers not matching any bundle attribute are logged
+/// as warnings and their cells are ignored.
+///
+/// Returns the validation status of each record along with its 1-based number, the header row
+/// excluded.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Csv)` if the CSV can't be read.
+pub fn validate_data_csv(
+    oca: &OCABundle,
+    csv_reader: impl Read,
+) -> Result<Vec<(usize, DataValidationStatus)>, OcaSdkError> {
+    let oca_box = validation_box(oca)?;
+    let mut reader = csv::Reader::from_reader(csv_reader);
+
+    let headers = reader.headers()?.clone();
+    for header in headers.iter() {
+        if !oca_box.attributes.contains_key(header) {
+            warn!(
+                "CSV header \"{}\" doesn't match any bundle attribute",
+                header
+            );
+        }
+    }
+
+    reader
+        .records()
+        .enumerate()
+        .map(|(i, record)| {
+            let record = record?;
+            let data: Map<String, Value> = headers
+                .iter()
+                .zip(record.iter())
+                .filter(|(_, cell)| !cell.is_empty())
+                .filter_map(|(header, cell)| {
+                    let attribute = oca_box.attributes.get(header)?;
+                    Some((header.to_string(), csv_cell_to_value(attribute, cell)))
+                })
+                .collect();
+
+            let status = validate_with_box(
+                &oca_box,
+                &Value::Object(data),
+                &ValidationOptions::default(),
+            )?;
+            Ok((i + 1, status))
+        })
+        .collect()
+}
+
+/// Returns a copy of `data` with surrounding whitespace trimmed from the text values of
+/// code-like attributes, i.e. the values reported when
+/// [`ValidationOptions::forbid_surrounding_whitespace`] is set.
+pub fn normalize_data(oca: &OCABundle, data: &Value) -> Value {
+    let oca_box = bundle::declared_oca_box(oca.clone());
+
+    normalize_with_box(&oca_box, data)
+}
 
-    let oca_box = OCABox::from(oca.clone());
+/// Returns the `OCABox` to validate data against. Attributes missing from the capture base,
+/// though referred to by overlays, aren't validated.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::SkeletonBundle)` for a skeleton bundle, which has no attributes
+///   to validate the data against.
+fn validation_box(oca: &OCABundle) -> Result<OCABox, OcaSdkError> {
+    bundle::ensure_not_skeleton(oca)?;
+    Ok(bundle::declared_oca_box(oca.clone()))
+}
+
+fn attribute_by_name<'a>(oca_box: &'a OCABox, name: &str) -> Result<&'a Attribute, OcaSdkError> {
+    oca_box
+        .attributes
+        .get(name)
+        .ok_or_else(|| OcaSdkError::AttributeNotFound(name.to_string()))
+}
+
+fn validate_with_box(
+    oca_box: &OCABox,
+    data: &Value,
+    options: &ValidationOptions,
+) -> Result<DataValidationStatus, OcaSdkError> {
+    let errors = validate_attributes(oca_box, data, options, None)?;
 
+    Ok(capped_status(errors.into_iter().flatten(), options))
+}
+
+/// Validates each attribute of the bundle, returning errors grouped per attribute.
+///
+/// Objects referring to other bundles are validated against them if `refs` are given, see
+/// [`validate_data_with_refs`].
+fn validate_attributes(
+    oca_box: &OCABox,
+    data: &Value,
+    options: &ValidationOptions,
+    refs: Option<&HashMap<String, OCABundle>>,
+) -> Result<Vec<Vec<Violation>>, OcaSdkError> {
+    validate_attributes_timed(oca_box, data, options, refs, None)
+}
+
+/// Validates each attribute like [`validate_attributes`], recording the time spent on each of
+/// them in `timings` if given.
+fn validate_attributes_timed(
+    oca_box: &OCABox,
+    data: &Value,
+    options: &ValidationOptions,
+    refs: Option<&HashMap<String, OCABundle>>,
+    mut timings: Option<&mut HashMap<String, Duration>>,
+) -> Result<Vec<Vec<Violation>>, OcaSdkError> {
     if !data.is_object() {
-        return Err("Data is not an object".to_string());
+        return Ok(vec![vec![Violation::new(
+            Rule::Type,
+            DataValidationError::NotAnObject,
+        )]]);
+    }
+    if let Some(max_depth) = options.max_depth {
+        if exceeds_depth(data, max_depth) {
+            return Err(OcaSdkError::MaxDepthExceeded(max_depth));
+        }
     }
 
-    for attr in oca_box.attributes.values() {
-        let value = data.get(attr.name.clone());
-        let attribute_errors = validate_attribute(attr, value)?;
+    // Attributes are validated in alphabetical order, so that the errors kept once
+    // `max_errors` is exceeded are the same on every run.
+    let mut attributes: Vec<&Attribute> = oca_box.attributes.values().collect();
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
 
-        if !attribute_errors.is_empty() {
-            errors.extend(attribute_errors);
+    let mut errors = Vec::with_capacity(attributes.len());
+    let mut found = 0;
+    for attr in attributes {
+        let start = Instant::now();
+        let attr_errors = validate_attribute(attr, data.get(&attr.name), options, refs)?;
+        if let Some(timings) = timings.as_mut() {
+            timings.insert(attr.name.clone(), start.elapsed());
+        }
+        found += attr_errors.len();
+        errors.push(attr_errors);
+        if exceeds_max_errors(found, options) {
+            return Ok(errors);
         }
     }
+    if options.strict_unknown {
+        errors.push(unexpected_attributes(oca_box, data));
+    }
+
+    Ok(errors)
+}
+
+/// Returns `true` once more errors were found than [`ValidationOptions::max_errors`] allows,
+/// so that the validation can stop.
+fn exceeds_max_errors(found: usize, options: &ValidationOptions) -> bool {
+    options
+        .max_errors
+        .is_some_and(|max_errors| found > max_errors)
+}
 
+fn unexpected_attributes(oca_box: &OCABox, data: &Value) -> Vec<Violation> {
+    data.as_object()
+        .into_iter()
+        .flat_map(|object| object.keys())
+        .filter(|key| !oca_box.attributes.contains_key(*key))
+        .map(|key| {
+            Violation::new(
+                Rule::Type,
+                DataValidationError::UnexpectedAttribute {
+                    attribute: key.clone(),
+                },
+            )
+        })
+        .collect()
+}
+
+fn exceeds_depth(value: &Value, max_depth: usize) -> bool {
+    match value {
+        Value::Object(object) => {
+            max_depth == 0 || object.values().any(|v| exceeds_depth(v, max_depth - 1))
+        }
+        Value::Array(array) => {
+            max_depth == 0 || array.iter().any(|v| exceeds_depth(v, max_depth - 1))
+        }
+        _ => false,
+    }
+}
+
+fn status(violations: impl IntoIterator<Item = Violation>) -> DataValidationStatus {
+    let errors: Vec<DataValidationError> = violations.into_iter().map(|v| v.error).collect();
     if errors.is_empty() {
-        Ok(DataValidationStatus::Valid)
+        DataValidationStatus::Valid
     } else {
-        Ok(DataValidationStatus::Invalid(errors))
+        DataValidationStatus::Invalid(errors)
     }
 }
 
+/// Returns the status of the violations like [`status`], with the errors beyond
+/// [`ValidationOptions::max_errors`] suppressed.
+fn capped_status(
+    violations: impl IntoIterator<Item = Violation>,
+    options: &ValidationOptions,
+) -> DataValidationStatus {
+    status(capped(violations, options))
+}
+
+/// Returns the violations up to [`ValidationOptions::max_errors`], followed by a
+/// [`DataValidationError::Suppressed`] one counting the rest, if any.
+fn capped(
+    violations: impl IntoIterator<Item = Violation>,
+    options: &ValidationOptions,
+) -> Vec<Violation> {
+    let mut violations: Vec<Violation> = violations.into_iter().collect();
+    if let Some(max_errors) = options.max_errors {
+        if violations.len() > max_errors {
+            let count = violations.len() - max_errors;
+            violations.truncate(max_errors);
+            violations.push(Violation::new(
+                Rule::Type,
+                DataValidationError::Suppressed { count },
+            ));
+        }
+    }
+    violations
+}
+
+/// Validation rule source, i.e. the part of the bundle defining the rule.
+#[derive(Debug, Clone, Copy)]
+enum Rule {
+    /// Attribute type of the capture base.
+    Type,
+    Conformance,
+    EntryCode,
+    Format,
+}
+
+impl Rule {
+    fn overlay_type(self) -> Option<OverlayType> {
+        match self {
+            Rule::Type => None,
+            Rule::Conformance => Some(OverlayType::Conformance(String::new())),
+            Rule::EntryCode => Some(OverlayType::EntryCode(String::new())),
+            Rule::Format => Some(OverlayType::Format(String::new())),
+        }
+    }
+}
+
+/// Attribute validation failure along with the rule it violates.
+struct Violation {
+    rule: Rule,
+    error: DataValidationError,
+}
+
+impl Violation {
+    fn new(rule: Rule, error: DataValidationError) -> Self {
+        Self { rule, error }
+    }
+}
+
+fn normalize_with_box(oca_box: &OCABox, data: &Value) -> Value {
+    let mut normalized = data.clone();
+
+    if let Some(object) = normalized.as_object_mut() {
+        for attr in oca_box.attributes.values().filter(|a| is_code_like(a)) {
+            if let Some(Value::String(text)) = object.get_mut(&attr.name) {
+                if text.trim().len() != text.len() {
+                    *text = text.trim().to_string();
+                }
+            }
+        }
+    }
+
+    normalized
+}
+
+/// Converts a CSV cell according to the attribute type, leaving it as text if it can't be
+/// converted so that the type mismatch is reported by the validation.
+fn csv_cell_to_value(attribute: &Attribute, cell: &str) -> Value {
+    let text = || Value::String(cell.to_string());
+
+    match &attribute.attribute_type {
+        Some(NestedAttrType::Value(AttributeType::Numeric)) => cell
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| {
+                cell.parse::<f64>()
+                    .ok()
+                    .and_then(Number::from_f64)
+                    .map(Value::Number)
+            })
+            .unwrap_or_else(text),
+        Some(NestedAttrType::Value(AttributeType::Boolean)) => cell
+            .parse::<bool>()
+            .map(Value::Bool)
+            .unwrap_or_else(|_| text()),
+        Some(NestedAttrType::Array(_)) | Some(NestedAttrType::Reference(_)) => {
+            serde_json::from_str(cell).unwrap_or_else(|_| text())
+        }
+        _ => text(),
+    }
+}
+
+/// Checks the value of a numeric attribute against the [`NumericRange`] declared in the format
+/// overlay. Attributes without a range, or of other types, aren't checked.
+///
+/// The standard overlay only assigns URNs of standards to attributes, so it can't declare
+/// ranges.
+fn validate_attribute_constraints(attribute: &Attribute, value: &Value) -> Vec<Violation> {
+    let (Some(NestedAttrType::Value(AttributeType::Numeric)), Some(number)) =
+        (&attribute.attribute_type, value.as_f64())
+    else {
+        return vec![];
+    };
+    let Some(range) = attribute
+        .format
+        .as_deref()
+        .and_then(NumericRange::from_format)
+    else {
+        return vec![];
+    };
+
+    let mut errors = vec![];
+    if let Some(minimum) = range.min.filter(|min| number < *min) {
+        errors.push(Violation::new(
+            Rule::Format,
+            DataValidationError::BelowMinimum {
+                attribute: attribute.name.clone(),
+                value: value.to_string(),
+                minimum,
+            },
+        ));
+    }
+    if let Some(maximum) = range.max.filter(|max| number > *max) {
+        errors.push(Violation::new(
+            Rule::Format,
+            DataValidationError::AboveMaximum {
+                attribute: attribute.name.clone(),
+                value: value.to_string(),
+                maximum,
+            },
+        ));
+    }
+    errors
+}
+
+/// Decodes standard base64 or base64url, padded or not, as used e.g. by SAIDs.
+fn decode_base64(encoded: &str) -> Option<Vec<u8>> {
+    STANDARD
+        .decode(encoded)
+        .or_else(|_| URL_SAFE.decode(encoded))
+        .or_else(|_| URL_SAFE_NO_PAD.decode(encoded))
+        .ok()
+}
+
+/// Returns the maximum size in bytes of binary values declared in the format overlay as JSON,
+/// e.g. `{"max_size": 65536}`.
+fn format_max_size(attribute: &Attribute) -> Option<usize> {
+    let format: Value = serde_json::from_str(attribute.format.as_deref()?).ok()?;
+    format.get("max_size")?.as_u64().map(|size| size as usize)
+}
+
+/// Returns `true` if the format overlay declares the numeric attribute as an integer, e.g.
+/// `{"integer": true}`.
+fn format_integer(attribute: &Attribute) -> bool {
+    attribute
+        .format
+        .as_deref()
+        .and_then(|format| serde_json::from_str::<Value>(format).ok())
+        .and_then(|format| format.get("integer")?.as_bool())
+        .unwrap_or(false)
+}
+
+/// Returns `true` if the number has no fractional part, e.g. `3` or `3.0`.
+fn is_integer(number: &Number) -> bool {
+    number.is_i64() || number.is_u64() || number.as_f64().is_some_and(|n| n.fract() == 0.0)
+}
+
+/// Returns the maximum number of decimal places declared in the format overlay as JSON,
+/// e.g. `{"precision": 2}`.
+fn format_precision(attribute: &Attribute) -> Option<usize> {
+    let format: Value = serde_json::from_str(attribute.format.as_deref()?).ok()?;
+    format.get("precision")?.as_u64().map(|p| p as usize)
+}
+
+/// Returns the number of decimal places of the number, e.g. 5 for `3.14159` or `1e-5`.
+fn decimal_places(number: &Number) -> usize {
+    let text = number.to_string().to_lowercase();
+    let (mantissa, exponent) = match text.split_once('e') {
+        Some((mantissa, exponent)) => (mantissa, exponent.parse::<i64>().unwrap_or(0)),
+        None => (text.as_str(), 0),
+    };
+    let fraction = mantissa
+        .split_once('.')
+        .map_or(0, |(_, fraction)| fraction.trim_end_matches('0').len());
+
+    (fraction as i64 - exponent).max(0) as usize
+}
+
+/// Returns `true` if the format overlay declares the attribute as an ISO 4217 currency.
+fn is_currency(attribute: &Attribute) -> bool {
+    attribute
+        .format
+        .as_deref()
+        .is_some_and(|format| format.eq_ignore_ascii_case("ISO4217"))
+}
+
+/// Returns `true` if the format overlay declares the text attribute in the given format,
+/// compared case-insensitively.
+fn has_text_format(attribute: &Attribute, format: &str) -> bool {
+    matches!(
+        attribute.attribute_type,
+        Some(NestedAttrType::Value(AttributeType::Text))
+    ) && attribute
+        .format
+        .as_deref()
+        .is_some_and(|f| f.eq_ignore_ascii_case(format))
+}
+
+/// Returns `true` if the value is an absolute URI, or an IRI if `international` non-ASCII
+/// characters are allowed.
+fn is_uri(value: &str, international: bool) -> bool {
+    (international || value.is_ascii()) && url::Url::parse(value).is_ok()
+}
+
+/// Validates the object against the bundle referenced by the attribute, wrapping the errors
+/// found in [`DataValidationError::Nested`].
+fn validate_reference(
+    attribute: &Attribute,
+    reference: &RefValue,
+    index: Option<usize>,
+    object: &Value,
+    options: &ValidationOptions,
+    refs: &HashMap<String, OCABundle>,
+) -> Result<Vec<Violation>, OcaSdkError> {
+    let said = match reference {
+        RefValue::Said(said) => said.to_string(),
+        RefValue::Name(name) => name.clone(),
+    };
+    let bundle = refs
+        .get(&said)
+        .ok_or_else(|| OcaSdkError::MissingReference { said: said.clone() })?;
+    let nested = validate_attributes(&validation_box(bundle)?, object, options, Some(refs))?;
+
+    Ok(nested
+        .into_iter()
+        .flatten()
+        .map(|violation| Violation {
+            error: DataValidationError::Nested {
+                attribute: attribute.name.clone(),
+                index,
+                error: Box::new(violation.error),
+            },
+            ..violation
+        })
+        .collect())
+}
+
+fn is_of_type(value: &Value, attribute_type: &AttributeType) -> bool {
+    match attribute_type {
+        AttributeType::Text | AttributeType::DateTime | AttributeType::Binary => value.is_string(),
+        AttributeType::Numeric => value.is_number(),
+        AttributeType::Boolean => value.is_boolean(),
+    }
+}
+
+/// Returns `true` for text attributes constrained by entry codes or a format, whose values
+/// are codes rather than free text.
+fn is_code_like(attribute: &Attribute) -> bool {
+    matches!(
+        attribute.attribute_type,
+        Some(NestedAttrType::Value(AttributeType::Text))
+    ) && (attribute.entry_codes.is_some() || attribute.format.is_some())
+}
+
 fn validate_attribute(
     attribute: &Attribute,
     value: Option<&serde_json::Value>,
-) -> Result<Vec<String>, String> {
+    options: &ValidationOptions,
+    refs: Option<&HashMap<String, OCABundle>>,
+) -> Result<Vec<Violation>, OcaSdkError> {
     let mut errors = vec![];
+    let name = || attribute.name.clone();
 
     let is_required = attribute.conformance == Some("M".to_string());
 
@@ -79,98 +1535,320 @@ fn validate_attribute(
         Some(value) => value,
         None => {
             if is_required {
-                errors.push(format!(
-                    "Attribute \"{}\" value is mandatory",
-                    attribute.name
+                errors.push(Violation::new(
+                    Rule::Conformance,
+                    DataValidationError::MandatoryMissing { attribute: name() },
                 ));
             }
             return Ok(errors);
         }
     };
 
-    if v.is_array() || v.is_object() {
+    if let (Some(NestedAttrType::Reference(reference)), Some(refs), true) =
+        (&attribute.attribute_type, refs, v.is_object())
+    {
+        errors.extend(validate_reference(
+            attribute, reference, None, v, options, refs,
+        )?);
         return Ok(errors);
     }
 
-    if let Some(nested_attribute_type) = &attribute.attribute_type {
-        match nested_attribute_type {
-            NestedAttrType::Value(attribute_type) => match attribute_type {
-                AttributeType::Text => {
-                    if !v.is_string() {
-                        errors.push(format!(
-                            "Attribute \"{}\" value ({}) is not a string",
-                            attribute.name, v
-                        ));
-                    }
-                }
-                AttributeType::Numeric => {
-                    if !v.is_number() {
-                        errors.push(format!(
-                            "Attribute \"{}\" value ({}) is not a number",
-                            attribute.name, v
-                        ));
-                    }
-                }
-                AttributeType::DateTime => {
-                    if !v.is_string() {
-                        errors.push(format!(
-                            "Attribute \"{}\" value ({}) is not a string",
-                            attribute.name, v
-                        ));
-                    }
-                }
-                AttributeType::Boolean => {
-                    if !v.is_boolean() {
-                        errors.push(format!(
-                            "Attribute \"{}\" value ({}) is not a boolean",
-                            attribute.name, v
-                        ));
-                    }
-                }
-                AttributeType::Binary => {
-                    if !v.is_string() {
-                        errors.push(format!(
-                            "Attribute \"{}\" value ({}) is not a string",
-                            attribute.name, v
-                        ));
-                    }
-                }
-            },
-            NestedAttrType::Array(_) => {
-                if !v.is_array() {
-                    errors.push(format!(
-                        "Attribute \"{}\" value ({}) is not an array",
-                        attribute.name, v
+    // Elements of nested arrays aren't checked.
+    if let (Some(NestedAttrType::Array(element_type)), Value::Array(elements)) =
+        (&attribute.attribute_type, v)
+    {
+        if let (NestedAttrType::Reference(reference), Some(refs)) = (element_type.as_ref(), refs) {
+            for (index, element) in elements.iter().enumerate().filter(|(_, e)| e.is_object()) {
+                errors.extend(validate_reference(
+                    attribute,
+                    reference,
+                    Some(index),
+                    element,
+                    options,
+                    refs,
+                )?);
+            }
+        }
+        if let NestedAttrType::Value(element_type) = element_type.as_ref() {
+            for (index, element) in elements.iter().enumerate() {
+                if !is_of_type(element, element_type) {
+                    errors.push(Violation::new(
+                        Rule::Type,
+                        DataValidationError::ElementTypeMismatch {
+                            attribute: name(),
+                            index,
+                            expected: *element_type,
+                            got: element.to_string(),
+                        },
                     ));
                 }
             }
-            NestedAttrType::Null => {}
-            _ => {}
         }
+        return Ok(errors);
     }
 
-    if let Some(entry_codes) = &attribute.entry_codes {
-        match entry_codes {
-            EntryCodes::Array(codes) => {
-                if !codes.contains(&v.as_str().unwrap().to_string()) {
-                    errors.push(format!(
-                        "Attribute \"{}\" value ({}) is not in entry codes",
-                        attribute.name, v
-                    ));
-                }
+    if v.is_array() || v.is_object() {
+        return Ok(errors);
+    }
+
+    match &attribute.attribute_type {
+        Some(NestedAttrType::Value(attribute_type)) => {
+            if !is_of_type(v, attribute_type) {
+                errors.push(Violation::new(
+                    Rule::Type,
+                    DataValidationError::TypeMismatch {
+                        attribute: name(),
+                        expected: *attribute_type,
+                        got: v.to_string(),
+                    },
+                ));
+            }
+        }
+        Some(NestedAttrType::Array(_)) => {
+            errors.push(Violation::new(
+                Rule::Type,
+                DataValidationError::NotAnArray {
+                    attribute: name(),
+                    got: v.to_string(),
+                },
+            ));
+        }
+        _ => {}
+    }
+
+    if let (Some(NestedAttrType::Value(AttributeType::DateTime)), Some(datetime)) =
+        (&attribute.attribute_type, v.as_str())
+    {
+        let hint = attribute
+            .format
+            .as_deref()
+            .filter(|format| pattern::is_pattern(format));
+        let (valid, rule, format) = match (hint, &options.datetime_formats) {
+            (Some(hint), _) => (
+                DateTimeFormat::from_hint(hint).parses(datetime),
+                Rule::Format,
+                format!("a valid DateTime of format \"{}\"", hint),
+            ),
+            (None, Some(formats)) => (
+                formats.iter().any(|format| format.parses(datetime)),
+                Rule::Type,
+                "a valid DateTime".to_string(),
+            ),
+            (None, None) => (
+                [DateTimeFormat::Rfc3339, DateTimeFormat::Date]
+                    .iter()
+                    .any(|format| format.parses(datetime)),
+                Rule::Type,
+                "a valid DateTime".to_string(),
+            ),
+        };
+        if !valid {
+            errors.push(Violation::new(
+                rule,
+                DataValidationError::InvalidFormat {
+                    attribute: name(),
+                    value: v.to_string(),
+                    format,
+                },
+            ));
+        }
+    }
+
+    if options.forbid_surrounding_whitespace && is_code_like(attribute) {
+        if let Some(text) = v.as_str() {
+            if text.trim().len() != text.len() {
+                let rule = if attribute.entry_codes.is_some() {
+                    Rule::EntryCode
+                } else {
+                    Rule::Format
+                };
+                errors.push(Violation::new(
+                    rule,
+                    DataValidationError::SurroundingWhitespace {
+                        attribute: name(),
+                        value: v.to_string(),
+                    },
+                ));
             }
-            EntryCodes::Object(codes) => {
-                if !codes
-                    .values()
-                    .any(|c| c.contains(&v.as_str().unwrap().to_string()))
+        }
+    }
+
+    if let Value::Number(number) = v {
+        if format_integer(attribute) && !is_integer(number) {
+            errors.push(Violation::new(
+                Rule::Format,
+                DataValidationError::NotAnInteger {
+                    attribute: name(),
+                    value: v.to_string(),
+                },
+            ));
+        }
+    }
+
+    if let (Some(precision), Value::Number(number)) = (format_precision(attribute), v) {
+        if decimal_places(number) > precision {
+            errors.push(Violation::new(
+                Rule::Format,
+                DataValidationError::PrecisionExceeded {
+                    attribute: name(),
+                    value: v.to_string(),
+                    precision,
+                },
+            ));
+        }
+    }
+
+    errors.extend(validate_attribute_constraints(attribute, v));
+
+    if let (Some(NestedAttrType::Value(AttributeType::Binary)), Some(encoded)) =
+        (&attribute.attribute_type, v.as_str())
+    {
+        match decode_base64(encoded) {
+            Some(bytes) => {
+                if let Some(max_size) = format_max_size(attribute)
+                    .filter(|max_size| options.enforce_binary_size && bytes.len() > *max_size)
                 {
-                    errors.push(format!(
-                        "Attribute \"{}\" value ({}) is not in entry codes",
-                        attribute.name, v
+                    errors.push(Violation::new(
+                        Rule::Format,
+                        DataValidationError::BinaryTooLarge {
+                            attribute: name(),
+                            size: bytes.len(),
+                            max_size,
+                        },
                     ));
                 }
             }
-            _ => {}
+            None => errors.push(Violation::new(
+                Rule::Type,
+                DataValidationError::InvalidBase64 { attribute: name() },
+            )),
+        }
+    }
+
+    if is_currency(attribute) && attribute.entry_codes.is_none() {
+        if let Some(code) = v.as_str() {
+            if !iso4217::is_currency_code(code) {
+                errors.push(Violation::new(
+                    Rule::Format,
+                    DataValidationError::InvalidFormat {
+                        attribute: name(),
+                        value: v.to_string(),
+                        format: "an ISO 4217 currency code".to_string(),
+                    },
+                ));
+            }
+        }
+    }
+
+    if has_text_format(attribute, "ISO3166-1-alpha-2") && attribute.entry_codes.is_none() {
+        if let Some(code) = v.as_str() {
+            if !iso3166::is_country_code(code) {
+                errors.push(Violation::new(
+                    Rule::Format,
+                    DataValidationError::InvalidFormat {
+                        attribute: name(),
+                        value: v.to_string(),
+                        format: "a valid ISO 3166-1 alpha-2 country code".to_string(),
+                    },
+                ));
+            }
+        }
+    }
+
+    if has_text_format(attribute, "email") {
+        if let Some(email) = v.as_str() {
+            if !email::is_email_address(email) {
+                errors.push(Violation::new(
+                    Rule::Format,
+                    DataValidationError::InvalidFormat {
+                        attribute: name(),
+                        value: v.to_string(),
+                        format: "a valid email address".to_string(),
+                    },
+                ));
+            }
+        }
+    }
+
+    for (format, international) in [("URI", false), ("IRI", true)] {
+        if !has_text_format(attribute, format) {
+            continue;
+        }
+        if let Some(uri) = v.as_str() {
+            if !is_uri(uri, international) {
+                errors.push(Violation::new(
+                    Rule::Format,
+                    DataValidationError::InvalidFormat {
+                        attribute: name(),
+                        value: v.to_string(),
+                        format: format!("a valid {}", format),
+                    },
+                ));
+            }
+        }
+    }
+
+    if has_text_format(attribute, "UUID") {
+        if let Some(uuid) = v.as_str() {
+            if uuid::Uuid::parse_str(uuid).is_err() {
+                errors.push(Violation::new(
+                    Rule::Format,
+                    DataValidationError::InvalidFormat {
+                        attribute: name(),
+                        value: v.to_string(),
+                        format: "a valid UUID".to_string(),
+                    },
+                ));
+            }
+        }
+    }
+
+    if let (Some(NestedAttrType::Value(AttributeType::Text)), Some(format), Some(text)) = (
+        &attribute.attribute_type,
+        attribute.format.as_deref(),
+        v.as_str(),
+    ) {
+        if pattern::is_pattern(format) {
+            match pattern::compile(format) {
+                Ok(regex) if !regex.is_match(text) => errors.push(Violation::new(
+                    Rule::Format,
+                    DataValidationError::FormatMismatch {
+                        attribute: name(),
+                        value: v.to_string(),
+                        format: format.to_string(),
+                    },
+                )),
+                Ok(_) => {}
+                // A defect of the bundle rather than of the data, reported by
+                // `validate_semantics`, hence the format is skipped.
+                Err(e) => warn!(
+                    "Skipping format \"{}\" of attribute \"{}\": {}",
+                    format, attribute.name, e
+                ),
+            }
+        }
+    }
+
+    if let Some(entry_codes) = &attribute.entry_codes {
+        let allowed = match entry_codes {
+            EntryCodes::Array(codes) => Some(codes.clone()),
+            EntryCodes::Object(codes) => Some(codes.values().flatten().cloned().collect()),
+            _ => None,
+        };
+        if let Some(allowed) = allowed {
+            if !v
+                .as_str()
+                .is_some_and(|code| allowed.iter().any(|c| c == code))
+            {
+                errors.push(Violation::new(
+                    Rule::EntryCode,
+                    DataValidationError::EntryCodeViolation {
+                        attribute: name(),
+                        value: v.to_string(),
+                        allowed,
+                    },
+                ));
+            }
         }
     }
 
@@ -0,0 +1,52 @@
+//! Regular expressions assigned to text attributes by the format overlay.
+use regex::Regex;
+use std::cell::RefCell;
+use std::collections::VecDeque;
+
+/// Formats checked by dedicated validations rather than as regular expressions.
+const NAMED_FORMATS: &[&str] = &[
+    "ISO4217",
+    "ISO3166-1-alpha-2",
+    "email",
+    "UUID",
+    "URI",
+    "IRI",
+];
+
+/// Number of compiled patterns kept by each thread. Patterns come from bundles, so the cache
+/// is bounded for long-running validators seeing many of them.
+const CACHE_CAPACITY: usize = 128;
+
+thread_local! {
+    /// Compiled patterns, least recently used first.
+    static CACHE: RefCell<VecDeque<(String, Regex)>> = const { RefCell::new(VecDeque::new()) };
+}
+
+/// Returns `true` if the format of a text attribute is a regular expression, i.e. neither
+/// a named format nor JSON options like `{"precision": 2}`.
+pub(crate) fn is_pattern(format: &str) -> bool {
+    !format.trim_start().starts_with('{')
+        && !NAMED_FORMATS
+            .iter()
+            .any(|named| named.eq_ignore_ascii_case(format))
+}
+
+/// Compiles the pattern, reusing the regular expressions recently compiled by the thread.
+pub(crate) fn compile(pattern: &str) -> Result<Regex, regex::Error> {
+    CACHE.with(|cache| {
+        let mut cache = cache.borrow_mut();
+        if let Some(i) = cache.iter().position(|(p, _)| p == pattern) {
+            let entry = cache.remove(i).unwrap();
+            let regex = entry.1.clone();
+            cache.push_back(entry);
+            return Ok(regex);
+        }
+
+        let regex = Regex::new(pattern)?;
+        if cache.len() == CACHE_CAPACITY {
+            cache.pop_front();
+        }
+        cache.push_back((pattern.to_string(), regex.clone()));
+        Ok(regex)
+    })
+}
@@ -0,0 +1,75 @@
+use oca_ast_semantics::ast::OverlayType;
+use std::path::PathBuf;
+
+/// Errors returned by the SDK.
+#[derive(Debug, thiserror::Error)]
+pub enum OcaSdkError {
+    #[error("Capture base mismatch: expected {expected}, found {found}")]
+    CaptureBaseMismatch { expected: String, found: String },
+    #[error(
+        "Conflicting {overlay_type} overlay ({})",
+        .language.as_deref().unwrap_or("no language")
+    )]
+    OverlayConflict {
+        overlay_type: OverlayType,
+        language: Option<String>,
+    },
+    #[error("{0}")]
+    ValidationError(String),
+    #[error("Invalid OCA Bundle: {0}")]
+    Bundle(String),
+    #[error("Data exceeds maximum depth of {0}")]
+    MaxDepthExceeded(usize),
+    #[error("Failed to read {}: {source}", .path.display())]
+    Io {
+        path: PathBuf,
+        source: std::io::Error,
+    },
+    #[error("Failed to parse {}: {message}", .path.display())]
+    Parse { path: PathBuf, message: String },
+    #[error("Invalid OCA Bundle {}: {}", .path.display(), .errors.join(", "))]
+    InvalidBundle { path: PathBuf, errors: Vec<String> },
+    #[error("Unsupported JSON Schema: {0}")]
+    JsonSchema(String),
+    #[error("Invalid OCAFile: {0}")]
+    Ocafile(String),
+    #[error("Invalid JSON: {0}")]
+    Json(#[from] serde_json::Error),
+    #[error("Invalid CBOR: {0}")]
+    Cbor(#[from] ciborium::de::Error<std::io::Error>),
+    #[error(
+        "Invalid bundles in the collection: {}",
+        .0.iter().map(|(i, e)| format!("#{}: {}", i, e)).collect::<Vec<_>>().join("; ")
+    )]
+    BundleCollection(Vec<(usize, String)>),
+    #[error("Record #{index} can't be validated: {source}")]
+    Record {
+        index: usize,
+        source: Box<OcaSdkError>,
+    },
+    #[error("CSV error: {0}")]
+    Csv(#[from] csv::Error),
+    #[error("Bundle SAID couldn't be computed")]
+    SaidComputation,
+    #[error("Bundle encoding error: {0}")]
+    Encoding(#[from] said::version::error::Error),
+    #[error("Bundle encoding is not valid UTF-8: {0}")]
+    Utf8(#[from] std::string::FromUtf8Error),
+    #[error("Attribute \"{0}\" not found in the bundle")]
+    AttributeNotFound(String),
+    #[error("Referenced bundle {said} can't be resolved")]
+    MissingReference { said: String },
+    #[error("No overlays in {0} language")]
+    LanguageNotFound(String),
+    #[error("Invalid key: {0}")]
+    InvalidKey(String),
+    #[error("Invalid signature: {0}")]
+    InvalidSignature(String),
+    #[error("Invalid SAID {said}: {message}")]
+    InvalidSaid { said: String, message: String },
+    #[error("Bundle of capture base {0} is a skeleton without content")]
+    SkeletonBundle(String),
+    #[cfg(feature = "parquet")]
+    #[error("Parquet schema error: {0}")]
+    Parquet(#[from] parquet::errors::ParquetError),
+}
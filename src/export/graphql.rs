@@ -0,0 +1,83 @@
+use crate::{bundle::ensure_not_skeleton, OcaSdkError, WithInfo};
+use oca_ast_semantics::ast::{AttributeType, NestedAttrType};
+use oca_bundle_semantics::state::oca::OCABundle;
+
+/// Generates GraphQL SDL describing data captured with the bundle, as an object `type` for
+/// queries and a companion `input` type (suffixed with `Input`) for mutations.
+///
+/// Types are named after the bundle SAID, with characters not allowed in GraphQL names
+/// replaced by `_`. Attributes are mapped as follows:
+/// * `Text`, `DateTime`, `Binary` - `String`,
+/// * `Numeric` - `Float`,
+/// * `Boolean` - `Boolean`,
+/// * references - `String` holding the referenced data as JSON,
+/// * arrays - lists of the element type.
+///
+/// Mandatory attributes become non-nullable fields.
+///
+/// # Returns
+/// * `Ok(String)` - Schema in the GraphQL schema definition language.
+/// * `Err(OcaSdkError::ValidationError)` - If the bundle has no typed attributes, as GraphQL
+///   types need at least one field.
+/// * `Err(OcaSdkError::SkeletonBundle)` - If the bundle was created with
+///   [`skeleton_bundle`](crate::bundle::skeleton_bundle).
+pub fn to_graphql_schema(bundle: &OCABundle) -> Result<String, OcaSdkError> {
+    ensure_not_skeleton(bundle)?;
+    let info = bundle.info();
+    let mut attributes = info
+        .attributes()
+        .filter(|attr| !matches!(attr.attribute_type, None | Some(NestedAttrType::Null)))
+        .collect::<Vec<_>>();
+    if attributes.is_empty() {
+        return Err(OcaSdkError::ValidationError(
+            "Bundle has no attributes to expose in GraphQL schema".to_string(),
+        ));
+    }
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let name = bundle
+        .said
+        .as_ref()
+        .map(|said| sanitize(&said.to_string()))
+        .unwrap_or_else(|| "OCABundle".to_string());
+
+    let fields = attributes
+        .into_iter()
+        .map(|attr| {
+            let mut field_type = field_type(attr.attribute_type.as_ref().unwrap());
+            if attr.conformance.as_deref() == Some("M") {
+                field_type.push('!');
+            }
+            format!("  {}: {}\n", sanitize(&attr.name), field_type)
+        })
+        .collect::<String>();
+
+    Ok(format!(
+        "type {name} {{\n{fields}}}\n\ninput {name}Input {{\n{fields}}}\n"
+    ))
+}
+
+fn field_type(attribute_type: &NestedAttrType) -> String {
+    match attribute_type {
+        NestedAttrType::Value(attribute_type) => match attribute_type {
+            AttributeType::Text | AttributeType::DateTime | AttributeType::Binary => "String",
+            AttributeType::Numeric => "Float",
+            AttributeType::Boolean => "Boolean",
+        }
+        .to_string(),
+        NestedAttrType::Array(element_type) => format!("[{}]", field_type(element_type)),
+        NestedAttrType::Reference(_) | NestedAttrType::Null => "String".to_string(),
+    }
+}
+
+/// Makes the name a valid GraphQL name, `[_A-Za-z][_0-9A-Za-z]*`.
+fn sanitize(name: &str) -> String {
+    let mut sanitized = name
+        .chars()
+        .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+        .collect::<String>();
+    if !sanitized.starts_with(|c: char| c.is_ascii_alphabetic() || c == '_') {
+        sanitized.insert(0, '_');
+    }
+    sanitized
+}
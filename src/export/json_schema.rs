@@ -0,0 +1,86 @@
+use crate::WithInfo;
+use oca_ast_semantics::ast::{AttributeType, NestedAttrType};
+use oca_bundle_semantics::state::{entry_codes::EntryCodes, oca::OCABundle};
+use serde_json::{json, Map, Value};
+
+/// Generates a draft-07 JSON Schema describing data captured with the bundle, e.g. for JSON
+/// Schema validators or OpenAPI tooling.
+///
+/// Attributes are mapped as follows:
+/// * `Text` - `{"type": "string"}`,
+/// * `Numeric` - `{"type": "number"}`,
+/// * `Boolean` - `{"type": "boolean"}`,
+/// * `DateTime` - `{"type": "string", "format": "date-time"}`,
+/// * `Binary` - `{"type": "string", "contentEncoding": "base64"}`,
+/// * references - `{"type": "object"}`,
+/// * arrays - `{"type": "array"}` with `items` of the element type.
+///
+/// Entry codes become `enum`s, of the array items for arrays, and mandatory attributes are
+/// listed as `required`.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build_from_ocafile, export::json_schema::to_json_schema};
+///
+/// let bundle = build_from_ocafile(
+///     "ADD ATTRIBUTE name=Text\nADD CONFORMANCE ATTRS name=M".to_string(),
+/// ).unwrap();
+/// let schema = to_json_schema(&bundle);
+/// assert_eq!(schema["properties"]["name"]["type"], "string");
+/// assert_eq!(schema["required"][0], "name");
+/// ```
+pub fn to_json_schema(bundle: &OCABundle) -> Value {
+    let info = bundle.info();
+    let mut attributes = info
+        .attributes()
+        .filter(|attr| attr.attribute_type.is_some())
+        .collect::<Vec<_>>();
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let mut properties = Map::new();
+    let mut required = vec![];
+    for attr in attributes {
+        let entry_codes = attr.entry_codes.as_ref().and_then(|codes| match codes {
+            EntryCodes::Sai(_) => None,
+            EntryCodes::Array(codes) => Some(codes.clone()),
+            EntryCodes::Object(groups) => Some(groups.values().flatten().cloned().collect()),
+        });
+
+        let mut property = property(attr.attribute_type.as_ref().unwrap());
+        if let Some(codes) = entry_codes {
+            match property.get_mut("items") {
+                Some(items) => items["enum"] = json!(codes),
+                None => property["enum"] = json!(codes),
+            }
+        }
+        properties.insert(attr.name.clone(), property);
+
+        if attr.conformance.as_deref() == Some("M") {
+            required.push(attr.name.clone());
+        }
+    }
+
+    json!({
+        "$schema": "http://json-schema.org/draft-07/schema#",
+        "type": "object",
+        "properties": properties,
+        "required": required,
+    })
+}
+
+fn property(attribute_type: &NestedAttrType) -> Value {
+    match attribute_type {
+        NestedAttrType::Value(attribute_type) => match attribute_type {
+            AttributeType::Text => json!({"type": "string"}),
+            AttributeType::Numeric => json!({"type": "number"}),
+            AttributeType::Boolean => json!({"type": "boolean"}),
+            AttributeType::DateTime => json!({"type": "string", "format": "date-time"}),
+            AttributeType::Binary => json!({"type": "string", "contentEncoding": "base64"}),
+        },
+        NestedAttrType::Array(element_type) => {
+            json!({"type": "array", "items": property(element_type)})
+        }
+        NestedAttrType::Reference(_) => json!({"type": "object"}),
+        NestedAttrType::Null => json!({"type": "null"}),
+    }
+}
@@ -0,0 +1,5 @@
+//! Conversions of OCA Bundles into schema definitions of other ecosystems.
+pub mod graphql;
+pub mod json_schema;
+#[cfg(feature = "parquet")]
+pub mod parquet;
@@ -0,0 +1,113 @@
+use crate::{bundle::ensure_not_skeleton, OcaSdkError, WithInfo};
+use ::parquet::{
+    basic::{LogicalType, Repetition, Type as PhysicalType},
+    errors::ParquetError,
+    schema::{
+        printer::print_schema,
+        types::{SchemaDescriptor, Type, TypePtr},
+    },
+};
+use oca_ast_semantics::ast::{AttributeType, NestedAttrType};
+use oca_bundle_semantics::state::oca::OCABundle;
+use std::sync::Arc;
+
+/// Generates Apache Parquet schema (message type) describing data captured with the bundle.
+///
+/// Attributes are mapped as follows:
+/// * `Text` - `BYTE_ARRAY` annotated as `UTF8`,
+/// * `Numeric` - `DOUBLE`,
+/// * `Boolean` - `BOOLEAN`,
+/// * `DateTime` - `BYTE_ARRAY` annotated as `UTF8`, as OCA date times are formatted strings,
+/// * `Binary` - `BYTE_ARRAY`,
+/// * references - `BYTE_ARRAY` annotated as `JSON`,
+/// * arrays - three-level `LIST` groups of the element type.
+///
+/// Mandatory attributes become `REQUIRED` fields, all others are `OPTIONAL`.
+///
+/// # Returns
+/// * `Ok(Vec<u8>)` - Schema in the Parquet message type notation.
+/// * `Err(OcaSdkError::Parquet)` - If the schema can't be built.
+/// * `Err(OcaSdkError::SkeletonBundle)` - If the bundle was created with
+///   [`skeleton_bundle`](crate::bundle::skeleton_bundle).
+pub fn to_parquet_schema(bundle: &OCABundle) -> Result<Vec<u8>, OcaSdkError> {
+    ensure_not_skeleton(bundle)?;
+    let info = bundle.info();
+    let mut attributes = info
+        .attributes()
+        .filter(|attr| !matches!(attr.attribute_type, None | Some(NestedAttrType::Null)))
+        .collect::<Vec<_>>();
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let fields = attributes
+        .into_iter()
+        .map(|attr| {
+            let repetition = if attr.conformance.as_deref() == Some("M") {
+                Repetition::REQUIRED
+            } else {
+                Repetition::OPTIONAL
+            };
+            field(
+                &attr.name,
+                attr.attribute_type.as_ref().unwrap(),
+                repetition,
+            )
+            .map(Arc::new)
+        })
+        .collect::<Result<Vec<TypePtr>, _>>()?;
+
+    let name = bundle
+        .said
+        .as_ref()
+        .map(|said| said.to_string())
+        .unwrap_or_else(|| "oca_bundle".to_string());
+    let root = Type::group_type_builder(&name)
+        .with_fields(fields)
+        .build()?;
+    let descriptor = SchemaDescriptor::new(Arc::new(root));
+
+    let mut schema = vec![];
+    print_schema(&mut schema, descriptor.root_schema());
+
+    Ok(schema)
+}
+
+fn field(
+    name: &str,
+    attribute_type: &NestedAttrType,
+    repetition: Repetition,
+) -> Result<Type, ParquetError> {
+    match attribute_type {
+        NestedAttrType::Value(attribute_type) => {
+            let (physical_type, logical_type) = match attribute_type {
+                AttributeType::Text | AttributeType::DateTime => {
+                    (PhysicalType::BYTE_ARRAY, Some(LogicalType::String))
+                }
+                AttributeType::Numeric => (PhysicalType::DOUBLE, None),
+                AttributeType::Boolean => (PhysicalType::BOOLEAN, None),
+                AttributeType::Binary => (PhysicalType::BYTE_ARRAY, None),
+            };
+            Type::primitive_type_builder(name, physical_type)
+                .with_repetition(repetition)
+                .with_logical_type(logical_type)
+                .build()
+        }
+        NestedAttrType::Array(element_type) => {
+            let element = field("element", element_type, Repetition::OPTIONAL)?;
+            let list = Type::group_type_builder("list")
+                .with_repetition(Repetition::REPEATED)
+                .with_fields(vec![Arc::new(element)])
+                .build()?;
+            Type::group_type_builder(name)
+                .with_repetition(repetition)
+                .with_logical_type(Some(LogicalType::List))
+                .with_fields(vec![Arc::new(list)])
+                .build()
+        }
+        NestedAttrType::Reference(_) | NestedAttrType::Null => {
+            Type::primitive_type_builder(name, PhysicalType::BYTE_ARRAY)
+                .with_repetition(repetition)
+                .with_logical_type(Some(LogicalType::Json))
+                .build()
+        }
+    }
+}
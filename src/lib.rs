@@ -8,17 +8,53 @@
 //! - Validate OCA Bundle semantics.
 //! - Validate data against OCA Bundle.
 //! - Traverse through OCA Bundle attributes.
+pub mod build;
+pub mod bundle;
+pub mod conversion;
 pub mod data_validator;
+mod error;
+pub mod export;
+pub mod migration;
+#[cfg(feature = "signature")]
+pub mod signature;
+#[cfg(feature = "test-data")]
+pub mod test_data;
+pub use error::OcaSdkError;
 pub use oca_ast_semantics::ast::{
     recursive_attributes::NestedAttrTypeFrame, AttributeType, NestedAttrType,
     OverlayType, RefValue,
 };
 
+use log::warn;
+use oca_bundle_semantics::state::oca::{capture_base::CaptureBase, DynOverlay};
+use oca_bundle_semantics::state::validator::Error as SemanticError;
+pub use oca_bundle_semantics::{
+    controller::load_oca as load,
+    state::{
+        attribute::Attribute,
+        oca::{overlay, OCABox, OCABundle},
+        validator::{SemanticValidationStatus, Validator as OCAValidator},
+    },
+};
+pub use oca_rs::facade::{
+    build::{build_from_ocafile, parse_oca_bundle_to_ocafile},
+    Facade,
+};
+use oca_rs::{EncodeBundle, HashFunctionCode, SerializationFormats};
+use serde::Serialize;
+use std::collections::{BTreeSet, HashMap, HashSet};
+use std::fmt;
+use std::fs;
+use std::mem::discriminant;
+use std::path::Path;
+use std::sync::{Arc, Mutex, OnceLock, Weak};
+
 /// Performs semantic validation of an `OCABundle` and returns a status
 /// indicating whether the validation succeeded or failed, along with any associated errors.
 ///
 /// Semantics validation ensures Bundle integrity, that is, that the Bundle identifier under `d`
-/// attribute matches the hash of the Bundle content.
+/// attribute matches the hash of the Bundle content. Formats of text attributes which aren't
+/// valid regular expressions are reported as well, see [`bundle::validate_format_overlay`].
 ///
 /// # Arguments
 /// * `oca_bundle` - A reference to an `OCABundle` instance to be validated.
@@ -27,10 +63,10 @@ pub use oca_ast_semantics::ast::{
 /// # Returns
 /// * `Ok(SemanticValidationStatus::Valid)` - If the `OCABundle` passes all semantic validation checks.
 /// * `Ok(SemanticValidationStatus::Invalid(errors))` - If validation errors are found, with a vector of error messages.
-/// * `Err(String)` - If a critical error occurs during validation.
+/// * `Err(OcaSdkError::Bundle)` - If a critical error occurs during validation.
 ///
 /// # Errors
-/// * Returns `Err` with a string message if the validation process encounters unexpected errors.
+/// * Returns `Err(OcaSdkError::Bundle)` if the validation process encounters unexpected errors.
 ///
 /// # Examples
 /// ```
@@ -55,38 +91,246 @@ pub use oca_ast_semantics::ast::{
 ///     }
 /// }
 /// ```
-pub use oca_bundle_semantics::state::validator::validate as validate_semantics;
-pub use oca_bundle_semantics::{
-    controller::load_oca as load,
-    state::{
-        attribute::Attribute,
-        oca::{overlay, OCABox, OCABundle},
-        validator::{SemanticValidationStatus, Validator as OCAValidator},
-    },
-};
-pub use oca_rs::facade::{
-    build::{build_from_ocafile, parse_oca_bundle_to_ocafile},
-    Facade,
-};
-use oca_rs::{EncodeBundle, HashFunctionCode, SerializationFormats};
-use std::collections::HashMap;
-use std::sync::{Arc, Mutex, Weak};
+pub fn validate_semantics(oca_bundle: &OCABundle) -> Result<SemanticValidationStatus, OcaSdkError> {
+    let mut errors = match oca_bundle_semantics::state::validator::validate(oca_bundle)
+        .map_err(OcaSdkError::Bundle)?
+    {
+        SemanticValidationStatus::Valid => vec![],
+        SemanticValidationStatus::Invalid(errors) => errors,
+    };
+    if let Err(format_errors) = bundle::validate_format_overlay(oca_bundle) {
+        errors.extend(format_errors.into_iter().map(SemanticError::Custom));
+    }
+
+    if errors.is_empty() {
+        Ok(SemanticValidationStatus::Valid)
+    } else {
+        Ok(SemanticValidationStatus::Invalid(errors))
+    }
+}
 
 pub trait ToJSON {
-    fn get_json_bundle(&self) -> String;
+    /// Serializes the bundle to JSON.
+    ///
+    /// # Errors
+    /// * Returns `Err(OcaSdkError::SkeletonBundle)` for a bundle created with
+    ///   [`skeleton_bundle`](bundle::skeleton_bundle).
+    /// * Returns `Err` if the bundle can't be encoded or the encoding isn't valid UTF-8.
+    fn get_json_bundle(&self) -> Result<String, OcaSdkError>;
+
+    /// Serializes the bundle to JSON like [`ToJSON::get_json_bundle`].
+    ///
+    /// # Panics
+    /// * Panics if the bundle can't be encoded or the encoding isn't valid UTF-8.
+    fn get_json_bundle_unchecked(&self) -> String {
+        self.get_json_bundle().unwrap()
+    }
+
+    /// Serializes the bundle to compact JSON with object keys sorted alphabetically at every
+    /// level, so that equal bundles always give the same string.
+    ///
+    /// The content is the same as of [`ToJSON::get_json_bundle`], which keeps the field order
+    /// of the OCA specification the SAIDs are computed over.
+    ///
+    /// # Errors
+    /// * Returns `Err` if the bundle can't be encoded.
+    fn canonical_json(&self) -> Result<String, OcaSdkError> {
+        let json: serde_json::Value = serde_json::from_str(&self.get_json_bundle()?)?;
+        Ok(serde_json::to_string(&sort_keys(json))?)
+    }
 }
 
 impl ToJSON for OCABundle {
-    fn get_json_bundle(&self) -> String {
+    fn get_json_bundle(&self) -> Result<String, OcaSdkError> {
+        bundle::ensure_not_skeleton(self)?;
         let code = HashFunctionCode::Blake3_256;
         let format = SerializationFormats::JSON;
 
-        String::from_utf8(self.encode(&code, &format).unwrap()).unwrap()
+        Ok(String::from_utf8(self.encode(&code, &format)?)?)
+    }
+}
+
+pub trait ToCBOR {
+    /// Serializes the bundle to CBOR.
+    ///
+    /// # Errors
+    /// * Returns `Err(OcaSdkError::SkeletonBundle)` for a bundle created with
+    ///   [`skeleton_bundle`](bundle::skeleton_bundle).
+    /// * Returns `Err` if the bundle can't be encoded.
+    fn get_cbor_bundle(&self) -> Result<Vec<u8>, OcaSdkError>;
+
+    /// Serializes the bundle to CBOR like [`ToCBOR::get_cbor_bundle`].
+    ///
+    /// # Panics
+    /// * Panics if the bundle can't be encoded.
+    fn get_cbor_bundle_unchecked(&self) -> Vec<u8> {
+        self.get_cbor_bundle().unwrap()
+    }
+}
+
+impl ToCBOR for OCABundle {
+    fn get_cbor_bundle(&self) -> Result<Vec<u8>, OcaSdkError> {
+        bundle::ensure_not_skeleton(self)?;
+        let code = HashFunctionCode::Blake3_256;
+        let format = SerializationFormats::CBOR;
+
+        Ok(self.encode(&code, &format)?)
+    }
+}
+
+/// Loads an OCA Bundle from CBOR, e.g. produced with [`ToCBOR::get_cbor_bundle`], like
+/// [`load`] does from JSON.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Cbor)` if the input isn't a CBOR encoded bundle.
+pub fn load_cbor(reader: &mut impl std::io::Read) -> Result<OCABundle, OcaSdkError> {
+    Ok(ciborium::from_reader(reader)?)
+}
+
+/// Loads an OCA Bundle from a JSON string, like [`load`] does from a reader.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Json)` if the string isn't a JSON encoded bundle.
+pub fn load_str(json: &str) -> Result<OCABundle, OcaSdkError> {
+    Ok(serde_json::from_str(json)?)
+}
+
+/// Loads an OCA Bundle from a JSON file, like [`load`] does from a reader.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Io)` if the file can't be read.
+/// * Returns `Err(OcaSdkError::Parse)` if the file isn't a JSON encoded bundle.
+pub fn load_file(path: impl AsRef<Path>) -> Result<OCABundle, OcaSdkError> {
+    let path = path.as_ref();
+    let json = fs::read_to_string(path).map_err(|source| OcaSdkError::Io {
+        path: path.to_path_buf(),
+        source,
+    })?;
+    serde_json::from_str(&json).map_err(|e| OcaSdkError::Parse {
+        path: path.to_path_buf(),
+        message: e.to_string(),
+    })
+}
+
+/// Returns the total length of the strings in the JSON value, object keys included.
+fn strings_len(value: &serde_json::Value) -> usize {
+    match value {
+        serde_json::Value::String(s) => s.len(),
+        serde_json::Value::Array(values) => values.iter().map(strings_len).sum(),
+        serde_json::Value::Object(map) => map.iter().map(|(k, v)| k.len() + strings_len(v)).sum(),
+        _ => 0,
+    }
+}
+
+fn sort_keys(value: serde_json::Value) -> serde_json::Value {
+    match value {
+        serde_json::Value::Object(map) => map
+            .into_iter()
+            .map(|(key, value)| (key, sort_keys(value)))
+            .collect::<std::collections::BTreeMap<_, _>>()
+            .into_iter()
+            .collect(),
+        serde_json::Value::Array(values) => values.into_iter().map(sort_keys).collect(),
+        value => value,
+    }
+}
+
+/// Loads OCA Bundles from a JSON array, e.g. a registry API response.
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::Json)` if the input isn't a JSON array.
+/// * Returns `Err(OcaSdkError::BundleCollection)` with the errors of all elements which aren't
+///   valid bundles, along with their indexes.
+pub fn load_bundle_collection(json_array: &str) -> Result<Vec<OCABundle>, OcaSdkError> {
+    let elements: Vec<serde_json::Value> = serde_json::from_str(json_array)?;
+
+    let mut bundles = vec![];
+    let mut errors = vec![];
+    for (i, element) in elements.into_iter().enumerate() {
+        match serde_json::from_value::<OCABundle>(element) {
+            Ok(bundle) => bundles.push(bundle),
+            Err(e) => errors.push((i, e.to_string())),
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(bundles)
+    } else {
+        Err(OcaSdkError::BundleCollection(errors))
+    }
+}
+
+/// Checks bundle integrity like [`validate_semantics`], without recomputing the SAIDs of the
+/// overlays of `skip_types`, e.g. to keep revalidation cheap while a schema is being authored.
+///
+/// The bundle SAID, the capture base SAID and the capture base references of all overlays are
+/// always checked. Conditional overlay and translation checks are left to
+/// [`validate_semantics`].
+///
+/// # Errors
+/// * Returns `Err(OcaSdkError::SkeletonBundle)` for a bundle created with
+///   [`skeleton_bundle`](bundle::skeleton_bundle).
+/// * Returns `Err(OcaSdkError::SaidComputation)` if the bundle SAID can't be computed.
+pub fn validate_semantics_partial(
+    bundle: &OCABundle,
+    skip_types: &[OverlayType],
+) -> Result<SemanticValidationStatus, OcaSdkError> {
+    let mut errors = vec![];
+
+    if bundle.said.as_ref().map(|said| said.to_string())
+        != Some(bundle::compute_bundle_said(bundle)?)
+    {
+        errors.push(SemanticError::Custom(
+            "OCA Bundle: Malformed SAID".to_string(),
+        ));
+    }
+
+    let capture_base = &bundle.capture_base;
+    let mut recalculated_capture_base = capture_base.clone();
+    recalculated_capture_base.sign();
+    if capture_base.said != recalculated_capture_base.said {
+        errors.push(SemanticError::Custom(
+            "capture_base: Malformed SAID".to_string(),
+        ));
+    }
+
+    for overlay in &bundle.overlays {
+        let subject = match overlay.language() {
+            Some(lang) => format!("{} ({})", overlay.overlay_type(), lang),
+            None => overlay.overlay_type().to_string(),
+        };
+
+        let skipped = skip_types
+            .iter()
+            .any(|t| discriminant(t) == discriminant(overlay.overlay_type()));
+        if !skipped {
+            let mut recalculated_overlay = overlay.clone();
+            recalculated_overlay.fill_said();
+            if overlay.said() != recalculated_overlay.said() {
+                errors.push(SemanticError::Custom(format!(
+                    "{}: Malformed SAID",
+                    subject
+                )));
+            }
+        }
+
+        if *overlay.capture_base() != capture_base.said {
+            errors.push(SemanticError::Custom(format!(
+                "{}: Mismatch capture_base SAI",
+                subject
+            )));
+        }
+    }
+
+    if errors.is_empty() {
+        Ok(SemanticValidationStatus::Valid)
+    } else {
+        Ok(SemanticValidationStatus::Invalid(errors))
     }
 }
 
 lazy_static::lazy_static! {
-    static ref INFO_CACHE: Mutex<HashMap<usize, Weak<OCABundleInfo>>> = Mutex::new(HashMap::new());
+    static ref INFO_CACHE: Mutex<HashMap<String, Weak<OCABundleInfo>>> = Mutex::new(HashMap::new());
 }
 
 pub trait WithInfo {
@@ -94,8 +338,12 @@ pub trait WithInfo {
 }
 
 impl WithInfo for OCABundle {
+    /// Info is cached by bundle SAID, as long as it's in use. Bundles without SAID get
+    /// freshly extracted info on every call.
     fn info(&self) -> Arc<OCABundleInfo> {
-        let key = self as *const OCABundle as usize;
+        let Some(key) = self.said.as_ref().map(|said| said.to_string()) else {
+            return Arc::new(OCABundleInfo::new(self));
+        };
         let mut cache = INFO_CACHE.lock().unwrap();
         if let Some(weak_info) = cache.get(&key) {
             if let Some(info) = weak_info.upgrade() {
@@ -103,68 +351,447 @@ impl WithInfo for OCABundle {
             }
         }
 
+        // Entries of bundles whose info is no longer in use are dropped along the way.
+        cache.retain(|_, weak_info| weak_info.strong_count() > 0);
         let new_info = Arc::new(OCABundleInfo::new(self));
         cache.insert(key, Arc::downgrade(&new_info));
         new_info
     }
 }
 
+/// Drops cached info of bundles which is no longer in use.
+pub fn invalidate_info_cache() {
+    INFO_CACHE
+        .lock()
+        .unwrap()
+        .retain(|_, weak_info| weak_info.strong_count() > 0);
+}
+
+/// Drops cached info of the bundle, so that the next [`WithInfo::info`] call extracts it
+/// anew. Info obtained earlier stays valid for its holders.
+pub fn invalidate_info(bundle: &OCABundle) {
+    if let Some(said) = &bundle.said {
+        INFO_CACHE.lock().unwrap().remove(&said.to_string());
+    }
+}
+
+/// Returns the overlays of type `T`, in their order.
+fn overlays_of_type<T: 'static>(overlays: &[DynOverlay]) -> Vec<&T> {
+    overlays
+        .iter()
+        .filter_map(|o| o.as_any().downcast_ref::<T>())
+        .collect()
+}
+
+/// Overlay occupying the same slot, i.e. type and language, as another overlay of the bundle.
+#[derive(Debug, Clone, PartialEq)]
+pub struct OverlayConflict {
+    pub overlay_type: OverlayType,
+    pub language: Option<String>,
+}
+
+impl fmt::Display for OverlayConflict {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.language {
+            Some(language) => write!(
+                f,
+                "{} overlay ({}) is defined more than once",
+                self.overlay_type, language
+            ),
+            None => write!(f, "{} overlay is defined more than once", self.overlay_type),
+        }
+    }
+}
+
+/// Information extracted from a bundle, obtained with [`WithInfo::info`].
+///
+/// The info is shared through an `Arc`; `(*bundle.info()).clone()` gives an owned copy.
+#[derive(Clone)]
 pub struct OCABundleInfo {
-    attributes: HashMap<String, Attribute>,
+    said: String,
+    oca_box: OCABox,
     pub meta: HashMap<String, HashMap<String, String>>,
-    pub links: Vec<overlay::Link>,
     pub framings: Vec<overlay::AttributeFraming>,
+    overlays: Vec<DynOverlay>,
+    conflicts: Vec<OverlayConflict>,
+    languages: Vec<String>,
+    capture_base_attributes: Vec<String>,
+    capture_base: CaptureBase,
+    links: OnceLock<Vec<overlay::Link>>,
 }
 
 impl OCABundleInfo {
+    /// Extracts the information from the bundle.
+    ///
+    /// Overlays duplicated for the same type and language are skipped, keeping the first one,
+    /// and logged as warnings. See [`OCABundleInfo::detect_overlay_conflicts`].
+    #[must_use = "OCABundleInfo::new creates an expensive info structure; bind it to a variable"]
     pub fn new(bundle: &OCABundle) -> Self {
+        let conflicts = bundle::overlay_conflicts(&bundle.overlays);
+        let mut seen = HashSet::new();
+        let mut deduplicated = bundle.clone();
+        deduplicated.overlays.retain(|overlay| {
+            if seen.insert(bundle::overlay_key(overlay)) {
+                return true;
+            }
+            let conflict = OverlayConflict {
+                overlay_type: overlay.overlay_type().clone(),
+                language: overlay.language().map(|l| l.to_639_3().to_string()),
+            };
+            warn!("{}, skipping the duplicate", conflict);
+            false
+        });
+
+        // Attributes referred to only by overlays are kept without a type.
+        let overlays = deduplicated.overlays.clone();
         let mut meta = HashMap::new();
-        let oca_box = OCABox::from(bundle.clone());
-        if let Some(m) = oca_box.meta {
+        let (oca_box, _) = bundle::into_oca_box_lenient(deduplicated);
+        if let Some(m) = &oca_box.meta {
             m.iter().for_each(|(k, v)| {
                 meta.insert(k.to_639_3().to_string(), v.to_owned());
             })
         }
 
-        let mut overlays = bundle.overlays.clone();
-        let links: Vec<overlay::Link> = overlays
-            .iter_mut()
-            .filter(|o| o.as_any().downcast_ref::<overlay::Link>().is_some())
-            .map(|o| {
-                o.as_any()
-                    .downcast_ref::<overlay::Link>()
-                    .unwrap()
-                    .to_owned()
-            })
+        let framings = overlays_of_type::<overlay::AttributeFraming>(&overlays)
+            .into_iter()
+            .cloned()
             .collect();
-        let framings: Vec<overlay::AttributeFraming> = overlays
-            .iter_mut()
-            .filter(|o| {
-                o.as_any()
-                    .downcast_ref::<overlay::AttributeFraming>()
-                    .is_some()
-            })
-            .map(|o| {
-                o.as_any()
-                    .downcast_ref::<overlay::AttributeFraming>()
-                    .unwrap()
-                    .to_owned()
-            })
+
+        let languages: BTreeSet<String> = overlays
+            .iter()
+            .filter_map(|o| o.language().map(|l| l.to_639_3().to_string()))
             .collect();
 
         Self {
-            attributes: oca_box.attributes,
+            said: bundle::said_to_string(&bundle.said),
+            oca_box,
             meta,
-            links,
             framings,
+            overlays,
+            conflicts,
+            languages: languages.into_iter().collect(),
+            capture_base_attributes: bundle.capture_base.attributes.keys().cloned().collect(),
+            capture_base: bundle.capture_base.clone(),
+            links: OnceLock::new(),
         }
     }
 
+    /// Returns the link overlays of the bundle, extracted on first access.
+    pub fn links(&self) -> &[overlay::Link] {
+        self.links.get_or_init(|| {
+            self.overlays::<overlay::Link>()
+                .into_iter()
+                .cloned()
+                .collect()
+        })
+    }
+
+    /// Returns the overlays of type `T`, e.g. `overlay::Label` or `overlay::Format`, in the
+    /// order of the bundle, skipped duplicates excluded.
+    ///
+    /// # Examples
+    /// ```
+    /// use oca_sdk_rs::{build_from_ocafile, overlay, WithInfo};
+    ///
+    /// let bundle = build_from_ocafile(
+    ///     "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+    /// ).unwrap();
+    /// let info = bundle.info();
+    /// let labels = info.overlays::<overlay::Label>();
+    /// assert_eq!(labels[0].attribute_labels["name"], "Name");
+    /// ```
+    pub fn overlays<T: overlay::Overlay + 'static>(&self) -> Vec<&T> {
+        overlays_of_type(&self.overlays)
+    }
+
+    /// Returns the names framed by the attribute framing overlays, e.g. attribute names of a
+    /// previous schema version, mapped to the attributes framing them.
+    pub fn framing_pairs(&self) -> HashMap<&str, &str> {
+        self.framings
+            .iter()
+            .flat_map(|framing| &framing.attribute_framing)
+            .flat_map(|(attribute, targets)| {
+                targets
+                    .keys()
+                    .map(move |framed| (framed.as_str(), attribute.as_str()))
+            })
+            .collect()
+    }
+
+    /// Returns overlays present in the bundle more than once for the same type and language.
+    /// Each conflict is reported once, regardless of the number of duplicates.
+    pub fn detect_overlay_conflicts(&self) -> Vec<OverlayConflict> {
+        self.conflicts.clone()
+    }
+
+    /// Returns the total number of overlays in the bundle, skipped duplicates excluded.
+    pub fn overlay_count(&self) -> usize {
+        self.overlays.len()
+    }
+
+    /// Returns the number of overlays of the given type, regardless of the overlay version.
+    pub fn overlay_count_for_type(&self, t: OverlayType) -> usize {
+        self.overlays
+            .iter()
+            .filter(|o| discriminant(o.overlay_type()) == discriminant(&t))
+            .count()
+    }
+
+    /// Returns the meta keys, e.g. `name` or `description`, defined in any language.
+    pub fn all_meta_keys(&self) -> HashSet<&str> {
+        self.meta
+            .values()
+            .flat_map(|meta| meta.keys().map(String::as_str))
+            .collect()
+    }
+
+    /// Returns ISO 639-3 codes of the languages defining the meta `key`, sorted.
+    pub fn languages_having_meta_key(&self, key: &str) -> Vec<&str> {
+        let mut languages: Vec<&str> = self
+            .meta
+            .iter()
+            .filter(|(_, meta)| meta.contains_key(key))
+            .map(|(lang, _)| lang.as_str())
+            .collect();
+        languages.sort();
+        languages
+    }
+
+    /// Returns the bundle name from the meta overlay in the `lang` language (ISO 639-3 code),
+    /// falling back to the first language, in alphabetical order, defining a name.
+    pub fn schema_name(&self, lang: &str) -> Option<&str> {
+        if let Some(name) = self.meta.get(lang).and_then(|m| m.get("name")) {
+            return Some(name);
+        }
+        self.languages_having_meta_key("name")
+            .first()
+            .and_then(|lang| self.meta[*lang].get("name"))
+            .map(String::as_str)
+    }
+
+    /// Returns the bundle name like [`OCABundleInfo::schema_name`], or `default` if no meta
+    /// overlay defines a name.
+    pub fn schema_name_or_default<'a>(&'a self, lang: &str, default: &'a str) -> &'a str {
+        self.schema_name(lang).unwrap_or(default)
+    }
+
+    /// Returns the number of distinct languages of the bundle overlays.
+    pub fn language_count(&self) -> usize {
+        self.languages.len()
+    }
+
+    /// Returns ISO 639-3 codes of the languages of the bundle overlays, sorted.
+    pub fn languages(&self) -> Vec<&str> {
+        self.languages.iter().map(String::as_str).collect()
+    }
+
     pub fn attributes(&self) -> impl Iterator<Item = &Attribute> {
-        self.attributes.values()
+        self.oca_box.attributes.values()
     }
 
     pub fn attribute(&self, name: &str) -> Option<&Attribute> {
-        self.attributes.get(name)
+        self.oca_box.attributes.get(name)
+    }
+
+    /// Returns the label of the attribute in the `lang` language, an ISO 639-3 (e.g. `fra`) or
+    /// ISO 639-1 (e.g. `fr`) code, falling back to English and then to the first language,
+    /// in alphabetical order of ISO 639-3 codes, labelling the attribute.
+    pub fn label(&self, attr: &str, lang: &str) -> Option<String> {
+        let labels = self.attribute(attr)?.labels.as_ref()?;
+        let find = |lang: &str| {
+            labels
+                .iter()
+                .find(|(l, _)| l.to_639_3() == lang || l.to_639_1() == Some(lang))
+        };
+        find(lang)
+            .or_else(|| find("eng"))
+            .or_else(|| labels.iter().min_by_key(|(l, _)| l.to_639_3()))
+            .map(|(_, label)| label.clone())
+    }
+
+    /// Returns the labels of all labelled attributes, keyed by attribute name, in the `lang`
+    /// language with the fallback of [`OCABundleInfo::label`].
+    pub fn labels(&self, lang: &str) -> HashMap<String, String> {
+        self.attributes()
+            .filter_map(|attr| Some((attr.name.clone(), self.label(&attr.name, lang)?)))
+            .collect()
+    }
+
+    /// Returns the attribute at the dot-separated path, e.g. `address.city`, descending
+    /// through the bundles referenced by the attributes on the path. The referenced bundles
+    /// are looked up in the registry by their SAID or name.
+    ///
+    /// Attribute names may contain dots themselves, so every split of the path is tried.
+    pub fn attribute_at_path<'a>(
+        &'a self,
+        path: &str,
+        registry: &'a HashMap<String, OCABundleInfo>,
+    ) -> Option<&'a Attribute> {
+        if let Some(attribute) = self.attribute(path) {
+            return Some(attribute);
+        }
+        path.match_indices('.').find_map(|(i, _)| {
+            let (name, rest) = (&path[..i], &path[i + 1..]);
+            let reference = match self.attribute(name)?.attribute_type.as_ref()? {
+                NestedAttrType::Reference(reference) => reference,
+                NestedAttrType::Array(item) => match item.as_ref() {
+                    NestedAttrType::Reference(reference) => reference,
+                    _ => return None,
+                },
+                _ => return None,
+            };
+            let key = match reference {
+                RefValue::Said(said) => said.to_string(),
+                RefValue::Name(name) => name.clone(),
+            };
+            registry.get(&key)?.attribute_at_path(rest, registry)
+        })
+    }
+
+    /// Returns the format the format overlay assigns to the attribute, e.g. a regular
+    /// expression for text attributes.
+    pub fn format(&self, name: &str) -> Option<&str> {
+        self.attribute(name)?.format.as_deref()
+    }
+
+    /// Returns the bounds of the numeric attribute declared in the format overlay, e.g.
+    /// `{"min": 0, "max": 120}`.
+    pub fn numeric_range(&self, name: &str) -> Option<data_validator::NumericRange> {
+        data_validator::NumericRange::from_format(self.format(name)?)
+    }
+
+    /// Returns `true` if the bundle has the attribute, either declared in the capture base or
+    /// only referred to by overlays.
+    pub fn has_attribute(&self, name: &str) -> bool {
+        self.oca_box.attributes.contains_key(name)
+    }
+
+    /// Returns `true` if the attribute is declared in the capture base, as opposed to
+    /// attributes only referred to by overlays, which have no type.
+    pub fn is_capture_base_attribute(&self, name: &str) -> bool {
+        self.capture_base_attributes.iter().any(|n| n == name)
+    }
+
+    /// Returns names of the attributes declared in the capture base, in their order.
+    pub fn capture_base_attribute_names(&self) -> impl Iterator<Item = &str> {
+        self.capture_base_attributes.iter().map(String::as_str)
+    }
+
+    /// Returns a compact, serializable summary of the bundle for front-end applications.
+    pub fn to_dto(&self) -> OCABundleInfoDto {
+        let mut attributes: Vec<AttributeDto> = self
+            .attributes()
+            .map(|attr| AttributeDto {
+                name: attr.name.clone(),
+                attribute_type: attr.attribute_type.as_ref().map(attribute_type_name),
+                conformance: attr.conformance.clone(),
+                labels: attr
+                    .labels
+                    .iter()
+                    .flatten()
+                    .map(|(lang, label)| (lang.to_639_3().to_string(), label.clone()))
+                    .collect(),
+            })
+            .collect();
+        attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+        let name = self
+            .meta
+            .iter()
+            .filter_map(|(lang, meta)| meta.get("name").map(|n| (lang.clone(), n.clone())))
+            .collect();
+
+        OCABundleInfoDto {
+            said: self.said.clone(),
+            attributes,
+            languages: self.languages.clone(),
+            name,
+        }
+    }
+
+    /// Serializes the capture base of the bundle, without its overlays, to JSON, e.g. to store
+    /// it separately as an immutable provenance record.
+    ///
+    /// # Errors
+    /// * Returns `Err(OcaSdkError::Json)` if the capture base can't be serialized.
+    pub fn capture_base_json(&self) -> Result<String, OcaSdkError> {
+        Ok(serde_json::to_string(&self.capture_base)?)
+    }
+
+    /// Returns a rough estimate of the memory used by the info in bytes, i.e. the total length
+    /// of the strings of its attributes, meta, links and framings, e.g. to bound the size of
+    /// a cache.
+    pub fn total_size_estimate(&self) -> usize {
+        let strings_len = |value: serde_json::Result<serde_json::Value>| {
+            value.map(|value| strings_len(&value)).unwrap_or_default()
+        };
+
+        let attributes: usize = self
+            .attributes()
+            .map(|attr| strings_len(serde_json::to_value(attr)))
+            .sum();
+        let meta: usize = self
+            .meta
+            .iter()
+            .flat_map(|(lang, meta)| {
+                std::iter::once(lang)
+                    .chain(meta.keys())
+                    .chain(meta.values())
+            })
+            .map(String::len)
+            .sum();
+
+        attributes
+            + meta
+            + strings_len(serde_json::to_value(self.links()))
+            + strings_len(serde_json::to_value(&self.framings))
+    }
+
+    /// Returns a copy of the `OCABox` the info was built from, e.g. to add attributes or
+    /// overlays and generate a new bundle with [`OCABox::generate_bundle`].
+    ///
+    /// Attributes only referred to by overlays are left out, as a bundle can't be generated
+    /// for attributes without a type; overlay entries for them are dropped with them.
+    #[must_use = "to_oca_box clones the whole OCABox; bind it to a variable"]
+    pub fn to_oca_box(&self) -> OCABox {
+        let mut oca_box = self.oca_box.clone();
+        oca_box
+            .attributes
+            .retain(|name, _| self.is_capture_base_attribute(name));
+        oca_box
+    }
+}
+
+/// Serializable summary of [`OCABundleInfo`], see [`OCABundleInfo::to_dto`].
+///
+/// The serialized form is kept stable across patch versions.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct OCABundleInfoDto {
+    pub said: String,
+    /// Attributes sorted by name.
+    pub attributes: Vec<AttributeDto>,
+    /// ISO 639-3 codes of the languages of the bundle overlays, sorted.
+    pub languages: Vec<String>,
+    /// Bundle name from the meta overlays, keyed by ISO 639-3 language code.
+    pub name: HashMap<String, String>,
+}
+
+/// Serializable summary of an [`Attribute`], part of [`OCABundleInfoDto`].
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct AttributeDto {
+    pub name: String,
+    /// Type in the OCAFile notation, e.g. `Text`, `Array[Numeric]` or `refs:<SAID>`.
+    pub attribute_type: Option<String>,
+    pub conformance: Option<String>,
+    /// Labels keyed by ISO 639-3 language code.
+    pub labels: HashMap<String, String>,
+}
+
+pub(crate) fn attribute_type_name(attribute_type: &NestedAttrType) -> String {
+    match attribute_type {
+        NestedAttrType::Value(value) => value.to_string(),
+        NestedAttrType::Reference(reference) => reference.to_string(),
+        NestedAttrType::Array(item) => format!("Array[{}]", attribute_type_name(item)),
+        NestedAttrType::Null => "Null".to_string(),
     }
 }
@@ -0,0 +1,76 @@
+//! Migration of captured data between versions of a schema.
+use crate::{bundle, WithInfo};
+use oca_bundle_semantics::state::oca::OCABundle;
+use serde_json::{Map, Value};
+use std::collections::HashMap;
+
+#[derive(Debug, thiserror::Error)]
+pub enum MigrationError {
+    #[error("Data is not an object")]
+    NotAnObject,
+    /// The new bundle has mandatory attributes missing from the data. The migrated `data`
+    /// holds `null` for each of them, to be filled in manually.
+    #[error("New mandatory attributes need values: {}", .attributes.join(", "))]
+    NewMandatoryAttributes {
+        attributes: Vec<String>,
+        data: Value,
+    },
+}
+
+/// Migrates data captured with `old_bundle` to `new_bundle`.
+///
+/// * Renamed attributes are recognized by the attribute framing overlays of `new_bundle`:
+///   an attribute framed with the name of an `old_bundle` attribute missing from
+///   `new_bundle` is its new name, and the value is moved accordingly.
+/// * Values of attributes removed in `new_bundle` are dropped. Keys unknown to both bundles
+///   are kept.
+/// * Mandatory attributes of `new_bundle` missing from the data are set to `null` and
+///   reported as [`MigrationError::NewMandatoryAttributes`].
+pub fn migrate_data(
+    old_bundle: &OCABundle,
+    new_bundle: &OCABundle,
+    data: &Value,
+) -> Result<Value, MigrationError> {
+    let object = data.as_object().ok_or(MigrationError::NotAnObject)?;
+    let old_box = bundle::declared_oca_box(old_bundle.clone());
+    let new_box = bundle::declared_oca_box(new_bundle.clone());
+
+    let info = new_bundle.info();
+    let renames: HashMap<&str, &str> = info
+        .framing_pairs()
+        .into_iter()
+        .filter(|(old_name, _)| {
+            old_box.attributes.contains_key(*old_name)
+                && !new_box.attributes.contains_key(*old_name)
+        })
+        .collect();
+
+    let mut migrated = Map::new();
+    for (key, value) in object {
+        if let Some(new_name) = renames.get(key.as_str()) {
+            migrated.insert(new_name.to_string(), value.clone());
+        } else if new_box.attributes.contains_key(key) || !old_box.attributes.contains_key(key) {
+            migrated.insert(key.clone(), value.clone());
+        }
+    }
+
+    let mut missing: Vec<String> = new_box
+        .attributes
+        .values()
+        .filter(|attr| attr.conformance.as_deref() == Some("M"))
+        .filter(|attr| !migrated.contains_key(&attr.name))
+        .map(|attr| attr.name.clone())
+        .collect();
+    missing.sort();
+
+    if missing.is_empty() {
+        return Ok(Value::Object(migrated));
+    }
+    for name in &missing {
+        migrated.insert(name.clone(), Value::Null);
+    }
+    Err(MigrationError::NewMandatoryAttributes {
+        attributes: missing,
+        data: Value::Object(migrated),
+    })
+}
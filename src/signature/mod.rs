@@ -0,0 +1,58 @@
+//! Ed25519 signatures of OCA Bundles, for deployments where bundles are signed with
+//! a controller's key.
+//!
+//! The signed payload is the JSON serialization of the bundle, as returned by
+//! [`ToJSON::get_json_bundle`], and signatures are base64 encoded.
+use crate::{OcaSdkError, ToJSON};
+use base64::{engine::general_purpose::STANDARD, Engine};
+use ed25519_dalek::{Signature, Signer, SigningKey, Verifier, VerifyingKey};
+use oca_bundle_semantics::state::oca::OCABundle;
+
+/// Signs the bundle with the 32 bytes long Ed25519 `private_key` (seed).
+///
+/// # Returns
+/// * `Ok(String)` - Base64 encoded signature.
+/// * `Err(OcaSdkError::InvalidKey)` - If the key is malformed.
+pub fn bundle_signature_sign(
+    bundle: &OCABundle,
+    private_key: &[u8],
+) -> Result<String, OcaSdkError> {
+    let secret_key = private_key
+        .try_into()
+        .map_err(|_| OcaSdkError::InvalidKey("private key must be 32 bytes long".to_string()))?;
+    let signing_key = SigningKey::from_bytes(secret_key);
+
+    let signature = signing_key.sign(bundle.get_json_bundle()?.as_bytes());
+
+    Ok(STANDARD.encode(signature.to_bytes()))
+}
+
+/// Verifies the base64 encoded `signature` of the bundle against the 32 bytes long Ed25519
+/// `public_key`.
+///
+/// # Returns
+/// * `Ok(true)` - If the signature is valid.
+/// * `Ok(false)` - If the signature doesn't match the bundle or the key.
+/// * `Err(OcaSdkError::InvalidKey)` - If the key is malformed.
+/// * `Err(OcaSdkError::InvalidSignature)` - If the signature is malformed.
+pub fn bundle_signature_verify(
+    bundle: &OCABundle,
+    signature: &str,
+    public_key: &[u8],
+) -> Result<bool, OcaSdkError> {
+    let public_key = public_key
+        .try_into()
+        .map_err(|_| OcaSdkError::InvalidKey("public key must be 32 bytes long".to_string()))?;
+    let verifying_key =
+        VerifyingKey::from_bytes(public_key).map_err(|e| OcaSdkError::InvalidKey(e.to_string()))?;
+
+    let signature = STANDARD
+        .decode(signature)
+        .map_err(|e| OcaSdkError::InvalidSignature(e.to_string()))?;
+    let signature = Signature::from_slice(&signature)
+        .map_err(|e| OcaSdkError::InvalidSignature(e.to_string()))?;
+
+    Ok(verifying_key
+        .verify(bundle.get_json_bundle()?.as_bytes(), &signature)
+        .is_ok())
+}
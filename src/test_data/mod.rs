@@ -0,0 +1,171 @@
+//! Generation of synthetic data matching an OCA Bundle, useful in testing pipelines.
+use crate::bundle;
+use crate::data_validator::{iso3166, iso4217, pattern, DateTimeFormat, NumericRange};
+use chrono::{DateTime, SecondsFormat, Utc};
+use oca_ast_semantics::ast::{AttributeType, NestedAttrType};
+use oca_bundle_semantics::state::{attribute::Attribute, entry_codes::EntryCodes, oca::OCABundle};
+use rand::{distributions::Alphanumeric, rngs::StdRng, Rng, SeedableRng};
+use serde_json::{Map, Number, Value};
+use std::fmt::Write;
+
+/// Options of the test data generation.
+#[derive(Debug, Clone, Default)]
+pub struct TestDataOptions {
+    /// Seed of the random generator. The same seed always produces the same data.
+    pub seed: Option<u64>,
+}
+
+/// Generates a data object with plausible values for each attribute of the bundle.
+///
+/// Attributes with entry codes get one of the codes. Otherwise, values are generated per
+/// attribute type: random strings for `Text` and `Binary`, numbers for `Numeric`, booleans for
+/// `Boolean` and RFC 3339 timestamps for `DateTime`. References are generated as empty objects.
+///
+/// Values follow the format overlay, so that the data is valid for the bundle: text matches
+/// the regular expression or the named format (e.g. `email` or `UUID`), numbers are integers
+/// within the `{"min", "max"}` range, and date times are formatted after hints like
+/// `YYYY-MM-DD`.
+///
+/// # Examples
+/// ```
+/// use oca_sdk_rs::{build_from_ocafile, test_data::{generate_test_data, TestDataOptions}};
+///
+/// let bundle = build_from_ocafile(
+///     "ADD ATTRIBUTE name=Text age=Numeric".to_string()
+/// ).unwrap();
+/// let options = TestDataOptions { seed: Some(42) };
+///
+/// let data = generate_test_data(&bundle, options.clone());
+/// assert!(data["name"].is_string());
+/// assert!(data["age"].is_number());
+/// assert_eq!(data, generate_test_data(&bundle, options));
+/// ```
+pub fn generate_test_data(oca: &OCABundle, options: TestDataOptions) -> Value {
+    let mut rng = match options.seed {
+        Some(seed) => StdRng::seed_from_u64(seed),
+        None => StdRng::from_entropy(),
+    };
+    let oca_box = bundle::declared_oca_box(oca.clone());
+
+    let mut attributes: Vec<&Attribute> = oca_box.attributes.values().collect();
+    attributes.sort_by(|a, b| a.name.cmp(&b.name));
+
+    let data: Map<String, Value> = attributes
+        .into_iter()
+        .map(|attr| (attr.name.clone(), generate_attribute_value(attr, &mut rng)))
+        .collect();
+
+    Value::Object(data)
+}
+
+fn generate_attribute_value(attribute: &Attribute, rng: &mut StdRng) -> Value {
+    let codes: Vec<&String> = match &attribute.entry_codes {
+        Some(EntryCodes::Array(codes)) => codes.iter().collect(),
+        Some(EntryCodes::Object(groups)) => groups.values().flatten().collect(),
+        _ => vec![],
+    };
+    if !codes.is_empty() {
+        return Value::String(codes[rng.gen_range(0..codes.len())].clone());
+    }
+
+    if let (Some(NestedAttrType::Value(attribute_type)), Some(format)) =
+        (&attribute.attribute_type, attribute.format.as_deref())
+    {
+        if let Some(value) = generate_formatted_value(attribute_type, format, rng) {
+            return value;
+        }
+    }
+
+    match &attribute.attribute_type {
+        Some(attribute_type) => generate_value(attribute_type, rng),
+        None => Value::Null,
+    }
+}
+
+/// Generates a value of the format assigned by the format overlay, or `None` for formats
+/// which values generated per type already follow, e.g. `{"precision": 2}`.
+fn generate_formatted_value(
+    attribute_type: &AttributeType,
+    format: &str,
+    rng: &mut StdRng,
+) -> Option<Value> {
+    match attribute_type {
+        AttributeType::Text => generate_formatted_text(format, rng).map(Value::String),
+        AttributeType::Numeric => {
+            NumericRange::from_format(format).map(|range| generate_number(&range, rng))
+        }
+        AttributeType::DateTime if pattern::is_pattern(format) => {
+            let DateTimeFormat::Custom(format) = DateTimeFormat::from_hint(format) else {
+                return None;
+            };
+            let mut datetime = String::new();
+            write!(datetime, "{}", random_datetime(rng).format(&format)).ok()?;
+            Some(Value::String(datetime))
+        }
+        _ => None,
+    }
+}
+
+fn generate_formatted_text(format: &str, rng: &mut StdRng) -> Option<String> {
+    let pick =
+        |codes: Vec<&str>, rng: &mut StdRng| codes[rng.gen_range(0..codes.len())].to_string();
+    let text = match format.to_ascii_lowercase().as_str() {
+        "email" => format!("{}@example.com", random_string(rng, 8).to_lowercase()),
+        "uri" | "iri" => format!("https://example.com/{}", random_string(rng, 8)),
+        "uuid" => uuid::Builder::from_random_bytes(rng.gen())
+            .into_uuid()
+            .to_string(),
+        "iso4217" => pick(iso4217::CODES.to_vec(), rng),
+        "iso3166-1-alpha-2" => pick(iso3166::ALPHA_2_CODES.iter().copied().collect(), rng),
+        _ if pattern::is_pattern(format) => {
+            let regex = rand_regex::Regex::compile(format, 8).ok()?;
+            rng.sample::<String, _>(&regex)
+        }
+        _ => return None,
+    };
+
+    Some(text)
+}
+
+/// Generates an integer within the range, or its minimum if the range holds no integers.
+fn generate_number(range: &NumericRange, rng: &mut StdRng) -> Value {
+    let min = range.min.unwrap_or(match range.max {
+        Some(max) if max < 0.0 => max - 1000.0,
+        _ => 0.0,
+    });
+    let max = range.max.unwrap_or(min + 1000.0);
+    let (low, high) = (min.ceil() as i64, max.floor() as i64);
+    if low <= high {
+        Value::from(rng.gen_range(low..=high))
+    } else {
+        Number::from_f64(min).map_or(Value::Null, Value::Number)
+    }
+}
+
+fn generate_value(attribute_type: &NestedAttrType, rng: &mut StdRng) -> Value {
+    match attribute_type {
+        NestedAttrType::Value(AttributeType::Text)
+        | NestedAttrType::Value(AttributeType::Binary) => Value::String(random_string(rng, 12)),
+        NestedAttrType::Value(AttributeType::Numeric) => Value::from(rng.gen_range(0..1000)),
+        NestedAttrType::Value(AttributeType::Boolean) => Value::Bool(rng.gen()),
+        NestedAttrType::Value(AttributeType::DateTime) => {
+            Value::String(random_datetime(rng).to_rfc3339_opts(SecondsFormat::Secs, true))
+        }
+        NestedAttrType::Array(item_type) => Value::Array(vec![generate_value(item_type, rng)]),
+        NestedAttrType::Reference(_) => Value::Object(Map::new()),
+        NestedAttrType::Null => Value::Null,
+    }
+}
+
+/// Returns a date time between 2000-01-01 and 2030-01-01.
+fn random_datetime(rng: &mut StdRng) -> DateTime<Utc> {
+    let timestamp = rng.gen_range(946_684_800..1_893_456_000);
+    DateTime::from_timestamp(timestamp, 0).unwrap_or_default()
+}
+
+fn random_string(rng: &mut StdRng, len: usize) -> String {
+    rng.sample_iter(&Alphanumeric)
+        .take(len)
+        .map(char::from)
+        .collect()
+}
@@ -0,0 +1,40 @@
+#![cfg(feature = "tokio")]
+use oca_sdk_rs::{
+    build_from_ocafile,
+    data_validator::{validate_data_async, AsyncCustomValidator, DataValidationStatus},
+    Attribute,
+};
+use serde_json::{json, Value};
+
+struct Registry;
+
+#[async_trait::async_trait]
+impl AsyncCustomValidator for Registry {
+    async fn validate(&self, attr: &Attribute, value: &Value) -> Vec<String> {
+        tokio::task::yield_now().await;
+        if attr.name == "i" && value != "issuer1" {
+            vec![format!("Issuer {} is not registered", value)]
+        } else {
+            vec![]
+        }
+    }
+}
+
+#[tokio::test]
+async fn validate_with_async_validators() {
+    let bundle = build_from_ocafile("ADD ATTRIBUTE d=Text i=Text".to_string()).unwrap();
+
+    let status = validate_data_async(&bundle, &json!({"i": "issuer1"}), vec![Box::new(Registry)])
+        .await
+        .unwrap();
+    assert!(matches!(status, DataValidationStatus::Valid));
+
+    let status = validate_data_async(&bundle, &json!({"i": "issuer2"}), vec![Box::new(Registry)])
+        .await
+        .unwrap();
+    assert!(matches!(
+        status,
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Issuer \"issuer2\" is not registered"])
+    ));
+}
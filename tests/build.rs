@@ -0,0 +1,335 @@
+use oca_sdk_rs::{
+    build::{
+        build_from_json_schema, build_from_ocafile_with_options, build_schemas_from_ocafile,
+        parse_oca_bundle_to_ocafile_opts, parse_ocafile_ast, BuildError, BuildOptions,
+        BuildWarning, CommandType, Directive, NestedValue, ObjectKind, OcaSpecVersion,
+        ParseToOcafileOptions,
+    },
+    build_from_ocafile, load, parse_oca_bundle_to_ocafile, AttributeType, NestedAttrType,
+    OCABundle, OcaSdkError, OverlayType, WithInfo,
+};
+use proptest::prelude::*;
+use std::fs;
+
+#[test]
+fn build_multiple_schemas() -> Result<(), Box<dyn std::error::Error>> {
+    let ocafile = fs::read_to_string("tests/assets/semantics/multi_schema.ocafile")?;
+
+    let bundles = build_schemas_from_ocafile(ocafile)?;
+    assert_eq!(bundles.len(), 2);
+
+    let person = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\n\
+         ADD LABEL en ATTRS name=\"Name\" age=\"Age\"\n\
+         ADD CONFORMANCE ATTRS name=M"
+            .to_string(),
+    )
+    .unwrap();
+    assert_eq!(bundles[0].said, person.said);
+    assert!(bundles[1]
+        .capture_base
+        .attributes
+        .contains_key("postal_code"));
+
+    Ok(())
+}
+
+#[test]
+fn build_single_schema_without_blocks() -> Result<(), Box<dyn std::error::Error>> {
+    let bundles = build_schemas_from_ocafile("ADD ATTRIBUTE name=Text".to_string())?;
+    assert_eq!(bundles.len(), 1);
+
+    Ok(())
+}
+
+#[test]
+fn report_dangling_overlay_references() {
+    let ocafile = "ADD ATTRIBUTE name=Text\n\
+                   ADD LABEL en ATTRS name=\"Name\" unknown_attr=\"Label\""
+        .to_string();
+
+    let result = build_from_ocafile_with_options(ocafile, &BuildOptions::default());
+    let Err(BuildError::WarningsAsErrors(warnings)) = result else {
+        panic!("expected dangling overlay reference");
+    };
+    assert!(matches!(
+        warnings.as_slice(),
+        [BuildWarning::DanglingOverlayReference { attribute_name, line: 2, .. }]
+            if attribute_name == "unknown_attr"
+    ));
+}
+
+#[test]
+fn reject_overlays_outside_spec_version() {
+    let ocafile = r#"ADD ATTRIBUTE name=Text
+ADD ATTR_FRAMING id="v1" ATTRS name={"full_name": {"predicate_id": "skos:exactMatch", "framing_justification": "semapv:ManualMappingCuration"}}"#;
+    let options = BuildOptions {
+        oca_spec_version: OcaSpecVersion::V1_0,
+        ..Default::default()
+    };
+
+    assert!(matches!(
+        build_from_ocafile_with_options(ocafile.to_string(), &options),
+        Err(BuildError::UnsupportedOverlayForVersion {
+            overlay_type: OverlayType::AttributeFraming(_),
+            minimum_version: OcaSpecVersion::V1_1,
+        })
+    ));
+    assert!(build_from_ocafile_with_options(ocafile.to_string(), &BuildOptions::default()).is_ok());
+}
+
+#[test]
+fn parse_ocafile_into_ast() {
+    let ocafile = r#"-- name=person
+ADD ATTRIBUTE name=Text age=Numeric
+
+ADD LABEL en ATTRS name="Name" age="Age"
+"#;
+
+    let ast = parse_ocafile_ast(ocafile).unwrap();
+    assert_eq!(ast.meta.get("name").map(String::as_str), Some("person"));
+    assert_eq!(ast.directives.len(), 2);
+    assert!(matches!(
+        ast.directives[0],
+        Directive {
+            kind: CommandType::Add,
+            object_kind: ObjectKind::CaptureBase(_),
+            line: 2,
+            ..
+        }
+    ));
+
+    let label = &ast.directives[1];
+    assert_eq!(label.line, 4);
+    let ObjectKind::Overlay(OverlayType::Label(_), content) = &label.object_kind else {
+        panic!("expected label overlay");
+    };
+    assert_eq!(
+        content
+            .attributes
+            .as_ref()
+            .and_then(|attrs| attrs.get("name")),
+        Some(&NestedValue::Value("Name".to_string()))
+    );
+
+    assert!(matches!(
+        parse_ocafile_ast("ADD ATTRIBUTE"),
+        Err(OcaSdkError::Ocafile(_))
+    ));
+}
+
+fn assert_round_trip(bundle: &OCABundle) {
+    let ocafile = parse_oca_bundle_to_ocafile(bundle);
+    let rebuilt = build_from_ocafile(ocafile.clone())
+        .unwrap_or_else(|e| panic!("can't build generated OCAFile: {e}\n{ocafile}"));
+    assert_eq!(rebuilt.said, bundle.said, "SAID changed for:\n{ocafile}");
+}
+
+#[test]
+fn round_trip_bundles_through_ocafile() -> Result<(), Box<dyn std::error::Error>> {
+    let ocafile = fs::read_to_string("tests/assets/semantics/entrance_credential.ocafile")?;
+    assert_round_trip(&build_from_ocafile(ocafile)?);
+
+    let ocafile = fs::read_to_string("tests/assets/semantics/multi_schema.ocafile")?;
+    for bundle in build_schemas_from_ocafile(ocafile)? {
+        assert_round_trip(&bundle);
+    }
+
+    let structural = fs::read_to_string("tests/assets/semantics/structural_bundle.json")?;
+    assert_round_trip(&load(&mut structural.as_bytes()).unwrap());
+
+    assert_round_trip(&build_from_ocafile(
+        r#"ADD ATTRIBUTE country=Text price=Numeric tags=Array[Text] born=DateTime
+ADD META en PROPS name="Product" description="Product on sale"
+ADD LABEL en ATTRS country="Country" price="Price"
+ADD LABEL pl ATTRS country="Kraj" price="Cena"
+ADD ENTRY_CODE ATTRS country=["PL", "DE"]
+ADD ENTRY en ATTRS country={"PL": "Poland", "DE": "Germany"}
+ADD FORMAT ATTRS price='{"precision": 2}' born="YYYY-MM-DD"
+ADD UNIT ATTRS price="EUR"
+ADD CONFORMANCE ATTRS country=M price=O"#
+            .to_string(),
+    )?);
+
+    Ok(())
+}
+
+#[test]
+fn prepend_saids_to_ocafile() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string())?;
+
+    let ocafile = parse_oca_bundle_to_ocafile_opts(&bundle, ParseToOcafileOptions::default())?;
+    assert_eq!(ocafile, parse_oca_bundle_to_ocafile(&bundle));
+    assert!(!ocafile.contains("SAID"));
+
+    let options = ParseToOcafileOptions {
+        include_saids: true,
+    };
+    let ocafile = parse_oca_bundle_to_ocafile_opts(&bundle, options)?;
+    let header = format!(
+        "# Bundle SAID: {}\n# Capture base SAID: {}\n",
+        bundle.said.as_ref().unwrap(),
+        bundle.capture_base.said.as_ref().unwrap()
+    );
+    assert!(ocafile.starts_with(&header));
+    assert_eq!(build_from_ocafile(ocafile)?.said, bundle.said);
+
+    Ok(())
+}
+
+fn attribute_type() -> impl Strategy<Value = &'static str> {
+    prop::sample::select(vec![
+        "Text",
+        "Numeric",
+        "Boolean",
+        "DateTime",
+        "Binary",
+        "Array[Text]",
+    ])
+}
+
+proptest! {
+    #[test]
+    fn round_trip_generated_bundles(
+        attributes in prop::collection::btree_map(
+            "[a-z][a-z0-9_]{0,8}",
+            (attribute_type(), "[A-Za-z][A-Za-z ]{0,8}", any::<bool>()),
+            1..6,
+        )
+    ) {
+        let declarations: Vec<_> = attributes
+            .iter()
+            .map(|(name, (attribute_type, _, _))| format!("{name}={attribute_type}"))
+            .collect();
+        let labels: Vec<_> = attributes
+            .iter()
+            .map(|(name, (_, label, _))| format!("{name}=\"{label}\""))
+            .collect();
+        let conformance: Vec<_> = attributes
+            .iter()
+            .map(|(name, (_, _, mandatory))| {
+                format!("{name}={}", if *mandatory { "M" } else { "O" })
+            })
+            .collect();
+        let ocafile = format!(
+            "ADD ATTRIBUTE {}\nADD LABEL en ATTRS {}\nADD CONFORMANCE ATTRS {}",
+            declarations.join(" "),
+            labels.join(" "),
+            conformance.join(" ")
+        );
+
+        let bundle = build_from_ocafile(ocafile).unwrap();
+        let rebuilt = build_from_ocafile(parse_oca_bundle_to_ocafile(&bundle)).unwrap();
+        prop_assert_eq!(rebuilt.said, bundle.said);
+    }
+}
+
+#[test]
+fn build_bundle_from_json_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_json_schema(
+        r#"{
+            "$schema": "https://json-schema.org/draft/2020-12/schema",
+            "title": "Person",
+            "description": "Person's details",
+            "type": "object",
+            "properties": {
+                "name": {"type": "string", "pattern": "^[A-Z]"},
+                "age": {"type": "integer", "minimum": 0},
+                "email": {"type": ["string", "null"], "format": "email"},
+                "born": {"type": "string", "format": "date"},
+                "country": {"type": "string", "enum": ["PL", "DE"]},
+                "tags": {"type": "array", "items": {"type": "string"}},
+                "photo": {"type": "string", "contentEncoding": "base64"},
+                "active": {"type": "boolean"}
+            },
+            "required": ["name", "age"]
+        }"#,
+    )?;
+    let info = bundle.info();
+
+    let type_of = |name: &str| bundle.capture_base.attributes[name].clone();
+    for (name, attribute_type) in [
+        ("name", AttributeType::Text),
+        ("age", AttributeType::Numeric),
+        ("email", AttributeType::Text),
+        ("born", AttributeType::DateTime),
+        ("photo", AttributeType::Binary),
+        ("active", AttributeType::Boolean),
+    ] {
+        assert_eq!(type_of(name), NestedAttrType::Value(attribute_type));
+    }
+    assert_eq!(
+        type_of("tags"),
+        NestedAttrType::Array(Box::new(NestedAttrType::Value(AttributeType::Text)))
+    );
+
+    let mut mandatory: Vec<_> = info
+        .attributes()
+        .filter(|attr| attr.conformance.as_deref() == Some("M"))
+        .map(|attr| attr.name.as_str())
+        .collect();
+    mandatory.sort();
+    assert_eq!(mandatory, vec!["age", "name"]);
+
+    assert_eq!(info.schema_name("eng"), Some("Person"));
+    assert_eq!(info.format("name"), Some("^[A-Z]"));
+    assert_eq!(info.format("email"), Some("email"));
+    assert_eq!(info.format("born"), Some("YYYY-MM-DD"));
+    assert_eq!(info.format("age"), Some(r#"{"integer":true,"min":0}"#));
+    assert!(info.attribute("country").unwrap().entry_codes.is_some());
+
+    assert!(matches!(
+        build_from_json_schema(r#"{"properties": {"address": {"type": "object"}}}"#),
+        Err(OcaSdkError::JsonSchema(_))
+    ));
+    assert!(matches!(
+        build_from_json_schema(r#"{"properties": {"first name": {"type": "string"}}}"#),
+        Err(OcaSdkError::JsonSchema(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn warn_about_non_iana_character_encodings() {
+    let ocafile = "ADD ATTRIBUTE name=Text photo=Binary\n\
+                   ADD CHARACTER_ENCODING ATTRS name=utf8 photo=base64"
+        .to_string();
+
+    let result = build_from_ocafile_with_options(ocafile, &BuildOptions::default());
+    let Err(BuildError::WarningsAsErrors(warnings)) = result else {
+        panic!("expected non-IANA character encoding");
+    };
+    assert!(matches!(
+        warnings.as_slice(),
+        [BuildWarning::NonIanaCharacterEncoding { attribute_name, encoding, line: 2 }, ..]
+            if attribute_name == "name" && encoding == "utf8"
+    ));
+
+    // Upstream accepts base64, so it builds with a warning, and fails in strict mode.
+    let ocafile = "ADD ATTRIBUTE name=Text photo=Binary\n\
+                   ADD CHARACTER_ENCODING ATTRS name=utf-8 photo=base64"
+        .to_string();
+    let result =
+        build_from_ocafile_with_options(ocafile.clone(), &BuildOptions::default()).unwrap();
+    assert!(matches!(
+        result.warnings.as_slice(),
+        [BuildWarning::NonIanaCharacterEncoding { attribute_name, encoding, line: 2 }]
+            if attribute_name == "photo" && encoding == "base64"
+    ));
+    let strict = BuildOptions {
+        strict: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        build_from_ocafile_with_options(ocafile, &strict),
+        Err(BuildError::WarningsAsErrors(warnings)) if warnings.len() == 1
+    ));
+
+    // Registry names match case-insensitively, so "utf-8" matches "UTF-8".
+    let ocafile = "ADD ATTRIBUTE name=Text\n\
+                   ADD CHARACTER_ENCODING ATTRS name=utf-8"
+        .to_string();
+    let result = build_from_ocafile_with_options(ocafile, &strict).unwrap();
+    assert!(result.warnings.is_empty());
+}
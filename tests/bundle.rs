@@ -0,0 +1,863 @@
+use oca_sdk_rs::{
+    build_from_ocafile,
+    bundle::{
+        audit_trail, bundle_ancestors, compare_schemas, compute_bundle_said, diff, from_oca_box,
+        into_oca_box, is_bundle_compatible_with, is_skeleton, is_subset_of, resolve_entry_codes,
+        skeleton_bundle, strip_overlays_for_language, summary, to_flat_json,
+        validate_bundle_completeness, validate_conformance_overlay, validate_structure, AuditTrail,
+        BundleRegistry, OverlayDiff,
+    },
+    invalidate_info, invalidate_info_cache, load, load_bundle_collection, load_cbor, load_file,
+    load_str, overlay, validate_semantics, validate_semantics_partial, OCABundleInfo, OcaSdkError,
+    OverlayConflict, OverlayType, SemanticValidationStatus, ToCBOR, ToJSON, WithInfo,
+};
+use std::collections::HashMap;
+use std::sync::Arc;
+
+#[test]
+fn bundle_compatibility() {
+    let v1 = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS name=M".to_string(),
+    )
+    .unwrap();
+    let v2 = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric nick=Text\nADD CONFORMANCE ATTRS name=M nick=O"
+            .to_string(),
+    )
+    .unwrap();
+    let v3 = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Text\nADD CONFORMANCE ATTRS name=M age=M".to_string(),
+    )
+    .unwrap();
+
+    let result = is_bundle_compatible_with(&v1, &v2);
+    assert!(result.forward_compatible);
+    assert!(result.backward_compatible);
+
+    let result = is_bundle_compatible_with(&v1, &v3);
+    assert!(!result.forward_compatible);
+    assert!(!result.backward_compatible);
+    assert_eq!(
+        result.breaking_changes,
+        vec![
+            "Attribute \"age\" type changed from Numeric to Text".to_string(),
+            "Attribute \"age\" became mandatory".to_string(),
+        ]
+    );
+}
+
+#[test]
+fn load_collection_of_bundles() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    let json = bundle.get_json_bundle()?;
+
+    let bundles = load_bundle_collection(&format!("[{}, {}]", json, json))?;
+    assert_eq!(bundles.len(), 2);
+    assert_eq!(bundles[1].said, bundle.said);
+
+    let result = load_bundle_collection(&format!("[{{}}, {}, 1]", json));
+    assert!(matches!(
+        result,
+        Err(OcaSdkError::BundleCollection(errors)) if errors.iter().map(|(i, _)| *i).eq([0, 2])
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn load_bundle_from_file_or_string() -> Result<(), Box<dyn std::error::Error>> {
+    let path = "tests/assets/semantics/structural_bundle.json";
+    let bundle = load_file(path)?;
+    assert_eq!(
+        bundle.said,
+        load(&mut std::fs::read_to_string(path)?.as_bytes())
+            .unwrap()
+            .said
+    );
+    assert_eq!(load_str(&bundle.get_json_bundle()?)?.said, bundle.said);
+
+    assert!(matches!(
+        load_file("tests/assets/semantics/missing.json"),
+        Err(OcaSdkError::Io { .. })
+    ));
+    assert!(matches!(
+        load_file("tests/assets/semantics/multi_schema.ocafile"),
+        Err(OcaSdkError::Parse { .. })
+    ));
+    assert!(matches!(load_str("{}"), Err(OcaSdkError::Json(_))));
+
+    Ok(())
+}
+
+#[test]
+fn compare_equivalent_schemas() {
+    let a = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"\nADD LABEL fr ATTRS name=\"Nom\""
+            .to_string(),
+    )
+    .unwrap();
+    let b = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\nADD LABEL fr ATTRS name=\"Nom\"\nADD LABEL en ATTRS name=\"Full name\""
+            .to_string(),
+    )
+    .unwrap();
+
+    let report = compare_schemas(&a, &b);
+    assert!(report.capture_base_match);
+    assert!(!report.structurally_identical);
+    assert_eq!(
+        report.overlay_differences,
+        vec![OverlayDiff::Changed {
+            overlay_type: OverlayType::Label("1.1".to_string()),
+            scope: Some("eng".to_string()),
+        }]
+    );
+}
+
+#[test]
+fn oca_box_round_trip() {
+    let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+
+    let rebuilt = from_oca_box(into_oca_box(bundle.clone()).unwrap()).unwrap();
+    assert_eq!(rebuilt.said, bundle.said);
+}
+
+#[test]
+fn compare_bundles_with_undeclared_attributes() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE full_name=Text\nADD LABEL en ATTRS full_name=\"Full name\"".to_string(),
+    )?;
+    let mut json: serde_json::Value = serde_json::from_str(&bundle.get_json_bundle()?)?;
+    json["overlays"]["label"][0]["attribute_labels"]["nickname"] = "Nickname".into();
+    let changed = load(&mut json.to_string().as_bytes())?;
+
+    assert!(matches!(
+        into_oca_box(changed.clone()),
+        Err(OcaSdkError::Bundle(message)) if message.contains("nickname")
+    ));
+    assert!(is_bundle_compatible_with(&bundle, &changed).forward_compatible);
+    assert!(is_subset_of(&changed, &bundle));
+    assert!(diff(&bundle, &changed).added.is_empty());
+
+    Ok(())
+}
+
+#[test]
+fn validate_conformance() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS name=M age=O".to_string(),
+    )
+    .unwrap();
+
+    assert!(validate_conformance_overlay(&bundle).is_ok());
+    assert!(validate_structure(&bundle).is_ok());
+}
+
+#[test]
+fn canonical_json_of_bundle() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD LABEL en ATTRS name=\"Name\" age=\"Age\""
+            .to_string(),
+    )?;
+
+    let canonical = bundle.canonical_json()?;
+    assert!(!canonical.contains(char::is_whitespace));
+    assert!(canonical.starts_with("{\"capture_base\":{\"attributes\":{\"age\""));
+
+    let json: serde_json::Value = serde_json::from_str(&bundle.get_json_bundle()?)?;
+    assert_eq!(serde_json::from_str::<serde_json::Value>(&canonical)?, json);
+
+    Ok(())
+}
+
+#[test]
+fn resolve_bundle_ancestors() {
+    let person = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    let employee = build_from_ocafile(format!(
+        "ADD ATTRIBUTE full_name=Text\nADD LINK refs:{} ATTRS full_name=\"name\"",
+        person.said.as_ref().unwrap()
+    ))
+    .unwrap();
+    let manager = build_from_ocafile(format!(
+        "ADD ATTRIBUTE manager_name=Text\nADD LINK refs:{} ATTRS manager_name=\"full_name\"",
+        employee.said.as_ref().unwrap()
+    ))
+    .unwrap();
+
+    let registry: BundleRegistry = [person.clone(), employee.clone()].into_iter().collect();
+    let ancestors = bundle_ancestors(&registry, &manager);
+
+    assert_eq!(
+        ancestors.iter().map(|b| &b.said).collect::<Vec<_>>(),
+        vec![&employee.said, &person.said]
+    );
+
+    // Both links lead to `person`, which is returned once.
+    let contractor = build_from_ocafile(format!(
+        "ADD ATTRIBUTE contractor_name=Text\nADD LINK refs:{} ATTRS contractor_name=\"name\"",
+        person.said.as_ref().unwrap()
+    ))
+    .unwrap();
+    let director = build_from_ocafile(format!(
+        "ADD ATTRIBUTE director_name=Text\n\
+         ADD LINK refs:{} ATTRS director_name=\"full_name\"\n\
+         ADD LINK refs:{} ATTRS director_name=\"contractor_name\"",
+        employee.said.as_ref().unwrap(),
+        contractor.said.as_ref().unwrap()
+    ))
+    .unwrap();
+    let registry: BundleRegistry = [person.clone(), employee.clone(), contractor.clone()]
+        .into_iter()
+        .collect();
+    let ancestors = bundle_ancestors(&registry, &director);
+    assert_eq!(ancestors.len(), 3);
+    assert!(ancestors.iter().any(|b| b.said == contractor.said));
+    assert_eq!(
+        ancestors.iter().filter(|b| b.said == person.said).count(),
+        1
+    );
+}
+
+#[test]
+fn capture_base_json_of_bundle() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+    )?;
+
+    let capture_base: serde_json::Value =
+        serde_json::from_str(&bundle.info().capture_base_json()?)?;
+    let json: serde_json::Value = serde_json::from_str(&bundle.get_json_bundle()?)?;
+    assert_eq!(capture_base, json["capture_base"]);
+
+    Ok(())
+}
+
+#[test]
+fn check_bundle_completeness() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\n\
+         ADD META en PROPS name=\"Person\"\n\
+         ADD LABEL en ATTRS name=\"Name\"\n\
+         ADD LABEL fr ATTRS name=\"Nom\""
+            .to_string(),
+    )
+    .unwrap();
+    let required = [
+        OverlayType::Meta("1.1".to_string()),
+        OverlayType::Label("1.1".to_string()),
+        OverlayType::Conformance("1.1".to_string()),
+    ];
+
+    assert_eq!(
+        validate_bundle_completeness(&bundle, &["en", "fra"], &required),
+        Err(vec![
+            "Missing Meta overlay in fra language".to_string(),
+            "Missing Conformance overlay".to_string(),
+        ])
+    );
+}
+
+#[test]
+fn flatten_bundle() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text country=Text\n\
+         ADD LABEL en ATTRS name=\"Name\" country=\"Country\"\n\
+         ADD CONFORMANCE ATTRS name=M\n\
+         ADD ENTRY_CODE ATTRS country=[\"DE\", \"PL\"]"
+            .to_string(),
+    )?;
+
+    let flat = to_flat_json(&bundle, "eng")?;
+    assert_eq!(
+        flat,
+        serde_json::json!({
+            "country": {
+                "type": "Text",
+                "required": false,
+                "label": "Country",
+                "entry_codes": ["DE", "PL"],
+                "format": null,
+            },
+            "name": {
+                "type": "Text",
+                "required": true,
+                "label": "Name",
+                "entry_codes": null,
+                "format": null,
+            },
+        })
+    );
+    assert!(matches!(
+        to_flat_json(&bundle, "fr"),
+        Err(OcaSdkError::LanguageNotFound(_))
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn bundle_subset() {
+    let consumer =
+        build_from_ocafile("ADD ATTRIBUTE name=Text\nADD CONFORMANCE ATTRS name=M".to_string())
+            .unwrap();
+    let provider = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS name=O".to_string(),
+    )
+    .unwrap();
+    let strict_provider = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS age=M".to_string(),
+    )
+    .unwrap();
+
+    assert!(is_subset_of(&consumer, &provider));
+    assert!(!is_subset_of(&provider, &consumer));
+    assert!(is_subset_of(&consumer, &strict_provider));
+    assert!(!is_subset_of(&provider, &strict_provider));
+}
+
+#[test]
+fn validate_format_regexes() {
+    let bundle =
+        build_from_ocafile("ADD ATTRIBUTE phone=Text\nADD FORMAT ATTRS phone=\"[0-9\"".to_string())
+            .unwrap();
+
+    assert!(matches!(
+        validate_structure(&bundle),
+        Err(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn cbor_round_trip() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+    )?;
+
+    let cbor = bundle.get_cbor_bundle()?;
+    let loaded = load_cbor(&mut cbor.as_slice())?;
+    assert_eq!(loaded.said, bundle.said);
+    assert_eq!(loaded.get_json_bundle()?, bundle.get_json_bundle()?);
+
+    Ok(())
+}
+
+#[test]
+fn validate_semantics_skipping_overlay_types() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+    )?;
+
+    let mut json: serde_json::Value = serde_json::from_str(&bundle.get_json_bundle()?)?;
+    json["overlays"]["label"][0]["attribute_labels"]["name"] = "Tampered".into();
+    let tampered = load(&mut json.to_string().as_bytes())?;
+    json["d"] = compute_bundle_said(&tampered)?.into();
+    let tampered = load(&mut json.to_string().as_bytes())?;
+
+    match validate_semantics_partial(&tampered, &[])? {
+        SemanticValidationStatus::Invalid(errors) => {
+            assert_eq!(errors.len(), 1);
+            let error = errors[0].to_string();
+            assert!(error.starts_with("Label (") && error.ends_with("): Malformed SAID"));
+        }
+        SemanticValidationStatus::Valid => panic!("tampered label not detected"),
+    }
+    assert!(matches!(
+        validate_semantics(&tampered)?,
+        SemanticValidationStatus::Invalid(_)
+    ));
+    assert!(matches!(
+        validate_semantics_partial(&tampered, &[OverlayType::Label("1.1".to_string())])?,
+        SemanticValidationStatus::Valid
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn skeleton_bundle_placeholder() {
+    let said = "EGQbKoJjLEcAfjkg3iW9tpwmnmidqxActG-dB0onEvqw";
+    let skeleton = skeleton_bundle(said).unwrap();
+    assert!(is_skeleton(&skeleton));
+    assert_eq!(
+        skeleton.capture_base.said.as_ref().unwrap().to_string(),
+        said
+    );
+    assert!(matches!(
+        skeleton.get_json_bundle(),
+        Err(OcaSdkError::SkeletonBundle(s)) if s == said
+    ));
+    assert!(matches!(
+        compute_bundle_said(&skeleton),
+        Err(OcaSdkError::SkeletonBundle(_))
+    ));
+
+    let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    assert!(!is_skeleton(&bundle));
+    assert!(matches!(
+        skeleton_bundle("not a said"),
+        Err(OcaSdkError::InvalidSaid { .. })
+    ));
+}
+
+#[test]
+fn diff_bundle_versions() {
+    let v1 = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric email=Text\nADD CONFORMANCE ATTRS name=O".to_string(),
+    )
+    .unwrap();
+    let v2 = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Text phone=Text\nADD CONFORMANCE ATTRS name=M".to_string(),
+    )
+    .unwrap();
+
+    let changes = diff(&v1, &v2);
+    assert_eq!(
+        changes
+            .added
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>(),
+        ["phone"]
+    );
+    assert_eq!(
+        changes
+            .removed
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>(),
+        ["email"]
+    );
+    assert_eq!(
+        changes
+            .modified
+            .iter()
+            .map(|c| c.name.as_str())
+            .collect::<Vec<_>>(),
+        ["age", "name"]
+    );
+    assert_eq!(changes.modified[1].old_conformance.as_deref(), Some("O"));
+    assert_eq!(changes.modified[1].new_conformance.as_deref(), Some("M"));
+    assert_eq!(changes.overlay_changes.len(), 1);
+
+    let unchanged = diff(&v1, &v1);
+    assert!(unchanged.added.is_empty() && unchanged.removed.is_empty());
+    assert!(unchanged.modified.is_empty() && unchanged.overlay_changes.is_empty());
+}
+
+#[test]
+fn resolve_entry_codes_by_said() {
+    let code_set = build_from_ocafile(
+        "ADD ATTRIBUTE status=Text\nADD ENTRY_CODE ATTRS status=[\"active\", \"closed\"]"
+            .to_string(),
+    )
+    .unwrap();
+    let code_set_said = code_set.said.clone().unwrap().to_string();
+    let form = build_from_ocafile(format!(
+        "ADD ATTRIBUTE status=Text\nADD ENTRY_CODE ATTRS status={}",
+        code_set_said
+    ))
+    .unwrap();
+    let grouped = build_from_ocafile(
+        "ADD ATTRIBUTE status=Text\nADD ENTRY_CODE ATTRS status={\"g1\": [\"a\"], \"g2\": [\"b\"]}"
+            .to_string(),
+    )
+    .unwrap();
+    let registry = BundleRegistry::from_iter([code_set.clone()]);
+
+    assert_eq!(
+        resolve_entry_codes(&form, "status", &registry).unwrap(),
+        ["active", "closed"]
+    );
+    assert_eq!(
+        resolve_entry_codes(&code_set, "status", &registry).unwrap(),
+        ["active", "closed"]
+    );
+    assert_eq!(
+        resolve_entry_codes(&grouped, "status", &registry).unwrap(),
+        ["a", "b"]
+    );
+    assert!(matches!(
+        resolve_entry_codes(&form, "status", &BundleRegistry::new()),
+        Err(OcaSdkError::MissingReference { said }) if said == code_set_said
+    ));
+    assert!(matches!(
+        resolve_entry_codes(&form, "missing", &registry),
+        Err(OcaSdkError::AttributeNotFound(_))
+    ));
+}
+
+#[test]
+fn estimate_bundle_info_size() {
+    let small = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    let large = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\nADD META en PROPS name=\"Person\" description=\"A person\"\nADD LABEL en ATTRS name=\"Full name\""
+            .to_string(),
+    )
+    .unwrap();
+
+    let small_size = small.info().total_size_estimate();
+    assert!(small_size >= "name".len());
+    assert!(large.info().total_size_estimate() > small_size + "A person".len());
+}
+
+#[test]
+fn extract_audit_trail() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\n\
+         ADD META en PROPS name=\"Person\" author=\"Jane Doe\" created_by_tool=\"oca-cli\" \
+         created_at=\"2024-05-01T12:30:00+02:00\"\n\
+         ADD META pl PROPS name=\"Osoba\" signed_by=\"did:key:z6Mk\""
+            .to_string(),
+    )
+    .unwrap();
+
+    let trail = audit_trail(&bundle);
+    assert_eq!(trail.author.as_deref(), Some("Jane Doe"));
+    assert_eq!(trail.created_by_tool.as_deref(), Some("oca-cli"));
+    assert_eq!(
+        trail.created_at.map(|t| t.to_rfc3339()).as_deref(),
+        Some("2024-05-01T10:30:00+00:00")
+    );
+    assert_eq!(trail.signed_by.as_deref(), Some("did:key:z6Mk"));
+
+    let plain = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    assert_eq!(audit_trail(&plain), AuditTrail::default());
+}
+
+#[test]
+fn overlays_of_given_type() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\n\
+         ADD LABEL en ATTRS name=\"Name\"\n\
+         ADD LABEL pl ATTRS name=\"Imię\"\n\
+         ADD FORMAT ATTRS name=\"^[A-Z]\""
+            .to_string(),
+    )
+    .unwrap();
+    let info = bundle.info();
+
+    assert_eq!(info.overlays::<overlay::Label>().len(), 2);
+    let formats = info.overlays::<overlay::Format>();
+    assert_eq!(formats.len(), 1);
+    assert_eq!(formats[0].attribute_formats["name"], "^[A-Z]");
+    assert!(info.overlays::<overlay::Meta>().is_empty());
+}
+
+#[test]
+fn info_of_bundle_replacing_another() {
+    let mut bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    let first_info = bundle.info();
+
+    // The new bundle takes the place of the dropped one in memory.
+    bundle = build_from_ocafile("ADD ATTRIBUTE age=Numeric".to_string()).unwrap();
+    let names: Vec<_> = bundle
+        .info()
+        .attributes()
+        .map(|attr| attr.name.clone())
+        .collect();
+
+    assert_eq!(names, vec!["age".to_string()]);
+    assert_ne!(first_info.attributes().next().unwrap().name, names[0]);
+    assert!(bundle.info().has_attribute("age"));
+    assert!(!bundle.info().has_attribute("name"));
+    assert!(first_info.has_attribute("name"));
+}
+
+#[test]
+fn invalidate_cached_info() {
+    let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    let info = bundle.info();
+    assert!(Arc::ptr_eq(&info, &bundle.info()));
+
+    invalidate_info_cache();
+    assert!(Arc::ptr_eq(&info, &bundle.info()));
+
+    invalidate_info(&bundle);
+    let fresh_info = bundle.info();
+    assert!(!Arc::ptr_eq(&info, &fresh_info));
+    assert!(Arc::ptr_eq(&fresh_info, &bundle.info()));
+}
+
+#[test]
+fn framing_pairs_of_renamed_attributes() {
+    let bundle = build_from_ocafile(
+        r#"ADD ATTRIBUTE last_name=Text age=Numeric
+ADD ATTR_FRAMING id="v1" ATTRS last_name={"surname": {"predicate_id": "skos:exactMatch", "framing_justification": "semapv:ManualMappingCuration"}}"#
+            .to_string(),
+    )
+    .unwrap();
+
+    let info = bundle.info();
+    let pairs = info.framing_pairs();
+    assert_eq!(pairs.len(), 1);
+    assert_eq!(pairs.get("surname"), Some(&"last_name"));
+
+    let plain = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    assert!(plain.info().framing_pairs().is_empty());
+}
+
+#[test]
+fn debug_summary_of_bundle() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD LABEL en ATTRS name=\"Name\"".to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        format!("{:?}", summary(&bundle)),
+        format!(
+            "OCABundle {{ said: \"{}\", attribute_count: 2, overlay_count: 1 }}",
+            bundle.said.as_ref().unwrap()
+        )
+    );
+}
+
+#[test]
+fn attribute_at_nested_path() {
+    let address = build_from_ocafile("ADD ATTRIBUTE city=Text zip=Text".to_string()).unwrap();
+    let address_said = address.said.clone().unwrap().to_string();
+    let person = build_from_ocafile(format!(
+        "ADD ATTRIBUTE name=Text address=refs:{0} previous=Array[refs:{0}]",
+        address_said
+    ))
+    .unwrap();
+    let registry = HashMap::from([(address_said, (*address.info()).clone())]);
+
+    let info = person.info();
+    assert_eq!(
+        info.attribute_at_path("address.city", &registry)
+            .map(|attribute| attribute.name.as_str()),
+        Some("city")
+    );
+    assert!(info.attribute_at_path("previous.zip", &registry).is_some());
+    assert!(info.attribute_at_path("name", &registry).is_some());
+    assert!(info
+        .attribute_at_path("address.unknown", &registry)
+        .is_none());
+    assert!(info.attribute_at_path("name.city", &registry).is_none());
+    assert!(info
+        .attribute_at_path("address.city", &HashMap::new())
+        .is_none());
+}
+
+#[test]
+fn labels_with_language_fallback() {
+    let bundle = build_from_ocafile(
+        r#"ADD ATTRIBUTE name=Text age=Numeric city=Text code=Text
+ADD LABEL en ATTRS name="Name" age="Age"
+ADD LABEL fr ATTRS name="Nom"
+ADD LABEL pl ATTRS city="Miasto"
+ADD LABEL de ATTRS city="Stadt""#
+            .to_string(),
+    )
+    .unwrap();
+
+    let info = bundle.info();
+    assert_eq!(info.label("name", "fra").as_deref(), Some("Nom"));
+    assert_eq!(info.label("name", "fr").as_deref(), Some("Nom"));
+    assert_eq!(info.label("age", "fra").as_deref(), Some("Age"));
+    assert_eq!(info.label("city", "fra").as_deref(), Some("Stadt"));
+    assert_eq!(info.label("code", "fra"), None);
+    assert_eq!(info.label("unknown", "fra"), None);
+
+    let labels = info.labels("fr");
+    assert_eq!(labels.len(), 3);
+    assert_eq!(labels["name"], "Nom");
+    assert_eq!(labels["age"], "Age");
+    assert_eq!(labels["city"], "Stadt");
+}
+
+#[test]
+fn distinguish_overlay_only_attributes() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE full_name=Text\nADD LABEL en ATTRS full_name=\"Full name\"".to_string(),
+    )?;
+    let mut json: serde_json::Value = serde_json::from_str(&bundle.get_json_bundle()?)?;
+    json["overlays"]["label"][0]["attribute_labels"]["nickname"] = "Nickname".into();
+    let bundle = load(&mut json.to_string().as_bytes())?;
+
+    // The info is built directly, as the SAID of the changed bundle is the original one.
+    let info = OCABundleInfo::new(&bundle);
+    assert!(info.has_attribute("full_name"));
+    assert!(info.is_capture_base_attribute("full_name"));
+    assert!(info.has_attribute("nickname"));
+    assert!(!info.is_capture_base_attribute("nickname"));
+    assert!(info.attribute("nickname").unwrap().attribute_type.is_none());
+    assert_eq!(
+        info.capture_base_attribute_names().collect::<Vec<_>>(),
+        ["full_name"]
+    );
+
+    Ok(())
+}
+
+#[test]
+fn oca_box_of_bundle_with_overlay_only_attribute() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE full_name=Text\nADD LABEL en ATTRS full_name=\"Full name\"".to_string(),
+    )?;
+    assert_eq!(from_oca_box(bundle.info().to_oca_box())?.said, bundle.said);
+
+    let mut json: serde_json::Value = serde_json::from_str(&bundle.get_json_bundle()?)?;
+    json["overlays"]["label"][0]["attribute_labels"]["nickname"] = "Nickname".into();
+    let changed = load(&mut json.to_string().as_bytes())?;
+
+    let oca_box = OCABundleInfo::new(&changed).to_oca_box();
+    assert!(!oca_box.attributes.contains_key("nickname"));
+    let rebuilt = from_oca_box(oca_box)?;
+    assert_eq!(
+        rebuilt.capture_base.attributes.keys().collect::<Vec<_>>(),
+        ["full_name"]
+    );
+    assert_eq!(rebuilt.said, bundle.said);
+
+    Ok(())
+}
+
+#[test]
+fn strip_overlays_of_other_languages() {
+    let bundle = build_from_ocafile(
+        r#"ADD ATTRIBUTE name=Text
+ADD META en PROPS name="Person"
+ADD META fr PROPS name="Personne"
+ADD LABEL en ATTRS name="Name"
+ADD LABEL fr ATTRS name="Nom"
+ADD INFORMATION en ATTRS name="Full name"
+ADD INFORMATION fr ATTRS name="Nom complet"
+ADD CONFORMANCE ATTRS name=M"#
+            .to_string(),
+    )
+    .unwrap();
+
+    let stripped = strip_overlays_for_language(&bundle, "fr").unwrap();
+    assert_ne!(stripped.said, bundle.said);
+    let info = stripped.info();
+    assert_eq!(info.languages(), ["fra"]);
+    assert_eq!(info.schema_name("fra"), Some("Personne"));
+    assert_eq!(info.label("name", "eng").as_deref(), Some("Nom"));
+    assert_eq!(stripped.overlays.len(), 4);
+    assert_eq!(
+        info.attribute("name").unwrap().conformance.as_deref(),
+        Some("M")
+    );
+
+    assert_eq!(
+        strip_overlays_for_language(&bundle, "fra").unwrap().said,
+        stripped.said
+    );
+    assert!(matches!(
+        strip_overlays_for_language(&bundle, "de"),
+        Err(OcaSdkError::LanguageNotFound(lang)) if lang == "de"
+    ));
+}
+
+#[test]
+fn detect_conflicting_meta_overlays() {
+    let mut bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\nADD META en PROPS name=\"Person\"".to_string(),
+    )
+    .unwrap();
+    assert!(bundle.info().detect_overlay_conflicts().is_empty());
+
+    let other =
+        build_from_ocafile("ADD ATTRIBUTE name=Text\nADD META en PROPS name=\"Human\"".to_string())
+            .unwrap();
+    let meta = other
+        .overlays
+        .iter()
+        .find(|o| matches!(o.overlay_type(), OverlayType::Meta(_)))
+        .unwrap();
+    bundle.overlays.push(meta.clone());
+    bundle.overlays.push(meta.clone());
+
+    // The info is built directly, as the SAID of the changed bundle is the original one.
+    let info = OCABundleInfo::new(&bundle);
+    assert!(matches!(
+        info.detect_overlay_conflicts().as_slice(),
+        [OverlayConflict { overlay_type: OverlayType::Meta(_), language: Some(language) }]
+            if language == "eng"
+    ));
+    assert_eq!(info.meta["eng"]["name"], "Person");
+}
+
+#[test]
+fn skip_duplicated_overlays_in_info() {
+    let mut bundle =
+        build_from_ocafile("ADD ATTRIBUTE name=Text\nADD LABEL en ATTRS name=\"Name\"".to_string())
+            .unwrap();
+    let overlay_count = bundle.info().overlay_count();
+
+    let label = bundle.overlays[0].clone();
+    bundle.overlays.push(label);
+
+    let info = OCABundleInfo::new(&bundle);
+    assert_eq!(info.overlay_count(), overlay_count);
+    assert_eq!(info.detect_overlay_conflicts().len(), 1);
+}
+
+#[test]
+fn count_bundle_languages() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\n\
+         ADD LABEL pl ATTRS name=\"Imię\"\n\
+         ADD LABEL en ATTRS name=\"Name\"\n\
+         ADD INFORMATION en ATTRS name=\"Full name\"\n\
+         ADD CONFORMANCE ATTRS name=M"
+            .to_string(),
+    )
+    .unwrap();
+    let info = bundle.info();
+    assert_eq!(info.language_count(), 2);
+    assert_eq!(info.languages(), ["eng", "pol"]);
+
+    let plain = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    assert_eq!(plain.info().language_count(), 0);
+    assert!(plain.info().languages().is_empty());
+}
+
+#[test]
+fn meta_keys_across_languages() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\n\
+         ADD META en PROPS name=\"Person\" description=\"A person\"\n\
+         ADD META pl PROPS name=\"Osoba\""
+            .to_string(),
+    )
+    .unwrap();
+    let info = bundle.info();
+
+    let mut keys: Vec<_> = info.all_meta_keys().into_iter().collect();
+    keys.sort();
+    assert_eq!(keys, ["description", "name"]);
+    assert_eq!(info.languages_having_meta_key("name"), ["eng", "pol"]);
+    assert_eq!(info.languages_having_meta_key("description"), ["eng"]);
+    assert!(info.languages_having_meta_key("author").is_empty());
+}
+
+#[test]
+fn count_overlays_of_type() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text\n\
+         ADD LABEL en ATTRS name=\"Name\"\n\
+         ADD LABEL pl ATTRS name=\"Imię\"\n\
+         ADD CONFORMANCE ATTRS name=M"
+            .to_string(),
+    )
+    .unwrap();
+    let info = bundle.info();
+
+    // Overlay versions are ignored.
+    assert_eq!(
+        info.overlay_count_for_type(OverlayType::Label("0.0".to_string())),
+        2
+    );
+    assert_eq!(
+        info.overlay_count_for_type(OverlayType::Conformance("9.9".to_string())),
+        1
+    );
+    assert_eq!(
+        info.overlay_count_for_type(OverlayType::Meta("1.1".to_string())),
+        0
+    );
+    assert_eq!(info.overlay_count(), 3);
+}
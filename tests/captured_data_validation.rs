@@ -1,8 +1,18 @@
 use oca_sdk_rs::{
     build_from_ocafile,
-    data_validator::{validate_data, DataValidationStatus},
-    load, validate_semantics, SemanticValidationStatus, ToJSON, WithInfo,
+    bundle::skeleton_bundle,
+    data_validator::{
+        normalize_data, validate_attribute_value, validate_batch, validate_batch_fail_fast,
+        validate_captures_data_file, validate_data, validate_data_and_explain, validate_data_csv,
+        validate_data_detailed, validate_data_partial, validate_data_scored,
+        validate_data_with_options, validate_data_with_refs, validate_data_with_refs_and_options,
+        validate_data_with_report, validate_field, validate_full, DataValidationError,
+        DataValidationStatus, DataValidator, DateTimeFormat, NumericRange, ValidationOptions,
+    },
+    load, validate_semantics, OcaSdkError, OverlayType, SemanticValidationStatus, ToJSON, WithInfo,
 };
+use serde_json::json;
+use std::collections::HashMap;
 use std::fs;
 use std::path::Path;
 
@@ -21,9 +31,9 @@ fn building_from_ocafile() -> Result<(), Box<dyn std::error::Error>> {
     oca_bundle.info().attributes().for_each(|attr| {
         println!("{:?}", attr);
     });
-    println!("links: {:?}", oca_bundle.info().links);
+    println!("links: {:?}", oca_bundle.info().links());
     println!("framings: {:?}", oca_bundle.info().framings);
-    println!("{}", oca_bundle.get_json_bundle());
+    println!("{}", oca_bundle.get_json_bundle()?);
 
     Ok(())
 }
@@ -58,3 +68,1124 @@ fn validate_captured_data() -> Result<(), Box<dyn std::error::Error>> {
 
     Ok(())
 }
+
+#[test]
+fn score_captured_data() -> Result<(), Box<dyn std::error::Error>> {
+    let data_str = fs::read_to_string(Path::new("tests/assets/data/nested.json"))?;
+    let data = serde_json::from_str(&data_str)?;
+    let structural_bundle_str =
+        fs::read_to_string(Path::new("tests/assets/semantics/structural_bundle.json"))?;
+    let structural_bundle = load(&mut structural_bundle_str.as_bytes()).unwrap();
+
+    let (status, score) = validate_data_scored(&structural_bundle, &data)?;
+    assert!(matches!(status, DataValidationStatus::Invalid(_)));
+    assert_eq!(
+        score.total_checks,
+        structural_bundle.capture_base.attributes.len()
+    );
+    assert_eq!(score.passed_checks, score.total_checks - 3);
+    assert!(score.score > 0.0 && score.score < 1.0);
+
+    Ok(())
+}
+
+#[test]
+fn validate_captured_data_file() {
+    let status = validate_captures_data_file(
+        Path::new("tests/assets/semantics/structural_bundle.json"),
+        Path::new("tests/assets/data/nested.json"),
+    )
+    .unwrap();
+    assert!(matches!(status, DataValidationStatus::Invalid(errors) if errors.len() == 3));
+
+    let err = validate_captures_data_file(
+        Path::new("tests/assets/semantics/missing.json"),
+        Path::new("tests/assets/data/nested.json"),
+    )
+    .unwrap_err();
+    assert!(err
+        .to_string()
+        .contains("tests/assets/semantics/missing.json"));
+}
+
+#[test]
+fn validate_single_attribute_value() -> Result<(), Box<dyn std::error::Error>> {
+    let structural_bundle_str =
+        fs::read_to_string(Path::new("tests/assets/semantics/structural_bundle.json"))?;
+    let structural_bundle = load(&mut structural_bundle_str.as_bytes()).unwrap();
+
+    assert!(matches!(
+        validate_attribute_value(&structural_bundle, "passed", &json!(true))?,
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_attribute_value(&structural_bundle, "passed", &json!("yes"))?,
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+    assert!(validate_attribute_value(&structural_bundle, "unknown", &json!(true)).is_err());
+
+    Ok(())
+}
+
+#[test]
+fn validate_datetime_format_hints() -> Result<(), Box<dyn std::error::Error>> {
+    let structural_bundle_str =
+        fs::read_to_string(Path::new("tests/assets/semantics/structural_bundle.json"))?;
+    let structural_bundle = load(&mut structural_bundle_str.as_bytes()).unwrap();
+
+    for (attribute, value) in [
+        ("time", json!("09:30 PM")),
+        ("time", json!("12:05 am")),
+        ("date", json!("01.05.2024")),
+    ] {
+        assert!(
+            matches!(
+                validate_attribute_value(&structural_bundle, attribute, &value)?,
+                DataValidationStatus::Valid
+            ),
+            "{} {} should be valid",
+            attribute,
+            value
+        );
+    }
+    for (attribute, value) in [
+        ("time", json!("21:30")),
+        ("time", json!("13:30 PM")),
+        ("date", json!("2024-05-01")),
+    ] {
+        assert!(
+            matches!(
+                validate_attribute_value(&structural_bundle, attribute, &value)?,
+                DataValidationStatus::Invalid(errors) if errors.len() == 1
+            ),
+            "{} {} should be invalid",
+            attribute,
+            value
+        );
+    }
+
+    Ok(())
+}
+
+#[test]
+fn bundle_info_dto() -> Result<(), Box<dyn std::error::Error>> {
+    let ocafile_str = fs::read_to_string(Path::new(
+        "tests/assets/semantics/entrance_credential.ocafile",
+    ))?;
+    let oca_bundle = build_from_ocafile(ocafile_str).unwrap();
+
+    let dto = oca_bundle.info().to_dto();
+    assert_eq!(dto.said, oca_bundle.said.clone().unwrap().to_string());
+    assert_eq!(dto.languages, vec!["eng".to_string()]);
+    assert_eq!(dto.name.get("eng").unwrap(), "Entrance credential");
+    assert_eq!(
+        dto.attributes
+            .iter()
+            .map(|a| a.name.as_str())
+            .collect::<Vec<_>>(),
+        vec!["d", "i", "passed"]
+    );
+    assert_eq!(serde_json::to_value(&dto)?["said"], json!(dto.said));
+
+    Ok(())
+}
+
+#[test]
+fn validate_csv_records() -> Result<(), Box<dyn std::error::Error>> {
+    let ocafile_str = fs::read_to_string(Path::new(
+        "tests/assets/semantics/entrance_credential.ocafile",
+    ))?;
+    let oca_bundle = build_from_ocafile(ocafile_str).unwrap();
+
+    let csv = "d,i,passed\nEF3,issuer1,true\nEF3,,maybe\n";
+    let results = validate_data_csv(&oca_bundle, csv.as_bytes())?;
+
+    assert_eq!(results.len(), 2);
+    assert!(matches!(results[0], (1, DataValidationStatus::Valid)));
+    assert!(matches!(
+        &results[1],
+        (2, DataValidationStatus::Invalid(errors)) if errors.len() == 2
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn validation_errors_point_to_overlays() -> Result<(), Box<dyn std::error::Error>> {
+    let data: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string("tests/assets/data/nested.json")?)?;
+    let structural_bundle_str =
+        fs::read_to_string(Path::new("tests/assets/semantics/structural_bundle.json"))?;
+    let structural_bundle = load(&mut structural_bundle_str.as_bytes()).unwrap();
+
+    let errors = validate_data_detailed(&structural_bundle, &data, &Default::default())?;
+    assert_eq!(errors.len(), 3);
+
+    let conformance_said = structural_bundle
+        .overlays
+        .iter()
+        .find(|o| matches!(o.overlay_type(), OverlayType::Conformance(_)))
+        .and_then(|o| o.said().clone())
+        .map(|said| said.to_string());
+    let missing_d = errors.iter().find(|e| e.attribute == "d").unwrap();
+    assert_eq!(missing_d.overlay_said, conformance_said);
+
+    let radio3 = errors.iter().find(|e| e.attribute == "radio3").unwrap();
+    assert_eq!(radio3.overlay_said, None);
+
+    Ok(())
+}
+
+#[test]
+fn reject_too_deep_data() -> Result<(), Box<dyn std::error::Error>> {
+    let data: serde_json::Value =
+        serde_json::from_str(&fs::read_to_string("tests/assets/data/nested.json")?)?;
+    let structural_bundle_str =
+        fs::read_to_string(Path::new("tests/assets/semantics/structural_bundle.json"))?;
+    let structural_bundle = load(&mut structural_bundle_str.as_bytes()).unwrap();
+
+    let options = ValidationOptions {
+        max_depth: Some(4),
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_data_with_options(&structural_bundle, &data, &options),
+        Err(OcaSdkError::MaxDepthExceeded(4))
+    ));
+
+    let options = ValidationOptions {
+        max_depth: Some(5),
+        ..Default::default()
+    };
+    assert!(validate_data_with_options(&structural_bundle, &data, &options).is_ok());
+
+    Ok(())
+}
+
+#[test]
+fn non_object_data_is_invalid() -> Result<(), Box<dyn std::error::Error>> {
+    let structural_bundle_str =
+        fs::read_to_string(Path::new("tests/assets/semantics/structural_bundle.json"))?;
+    let structural_bundle = load(&mut structural_bundle_str.as_bytes()).unwrap();
+
+    assert!(matches!(
+        validate_data(&structural_bundle, &json!(["issuer1"]))?,
+        DataValidationStatus::Invalid(errors) if errors == vec![DataValidationError::NotAnObject]
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn validate_currency_codes() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE currency=Text\nADD FORMAT ATTRS currency=\"ISO4217\"".to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"currency": "EUR"})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"currency": "EURO"})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn validate_country_codes() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE country=Text\nADD FORMAT ATTRS country=\"ISO3166-1-alpha-2\"".to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"country": "DE"})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    match validate_data(&bundle, &json!({"country": "ZZ"})).unwrap() {
+        DataValidationStatus::Invalid(errors) => assert_eq!(
+            errors[0].to_string(),
+            r#"Attribute "country" value ("ZZ") is not a valid ISO 3166-1 alpha-2 country code"#
+        ),
+        DataValidationStatus::Valid => panic!("ZZ isn't a country code"),
+    }
+    assert!(matches!(
+        validate_data(&bundle, &json!({"country": "de"})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn validate_decimal_precision() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE price=Numeric\nADD FORMAT ATTRS price='{\"precision\": 2}'".to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"price": 3.14})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"price": 3.14159})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn validate_uuids() {
+    let bundle =
+        build_from_ocafile("ADD ATTRIBUTE id=Text\nADD FORMAT ATTRS id=\"UUID\"".to_string())
+            .unwrap();
+
+    assert!(matches!(
+        validate_data(
+            &bundle,
+            &json!({"id": "67e55044-10b1-426f-9247-bb680e5fe0c8"})
+        )
+        .unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"id": "not-a-uuid"})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn warn_about_absent_optional_attributes() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text nick=Text age=Numeric\nADD CONFORMANCE ATTRS name=M".to_string(),
+    )
+    .unwrap();
+    let options = ValidationOptions {
+        require_all_attributes: true,
+        ..Default::default()
+    };
+
+    let result = validate_full(&bundle, &json!({"name": "Alice", "age": null}), &options).unwrap();
+    assert!(result.is_valid());
+    assert_eq!(
+        result.warnings,
+        vec!["Optional attribute \"nick\" is absent"]
+    );
+}
+
+#[test]
+fn validate_email_addresses() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE email=Text\nADD FORMAT ATTRS email=\"email\"".to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"email": "alice@example.com"})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"email": "not@"})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn bundle_schema_name() -> Result<(), Box<dyn std::error::Error>> {
+    let ocafile_str = fs::read_to_string(Path::new(
+        "tests/assets/semantics/entrance_credential.ocafile",
+    ))?;
+    let info = build_from_ocafile(ocafile_str).unwrap().info();
+
+    assert_eq!(info.schema_name("eng"), Some("Entrance credential"));
+    assert_eq!(info.schema_name("fra"), Some("Entrance credential"));
+    assert_eq!(
+        info.schema_name_or_default("fra", "Untitled"),
+        "Entrance credential"
+    );
+
+    let unnamed = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string())
+        .unwrap()
+        .info();
+    assert_eq!(unnamed.schema_name("eng"), None);
+    assert_eq!(
+        unnamed.schema_name_or_default("eng", "Untitled"),
+        "Untitled"
+    );
+
+    Ok(())
+}
+
+#[test]
+fn validate_referenced_objects() {
+    let address =
+        build_from_ocafile("ADD ATTRIBUTE city=Text\nADD CONFORMANCE ATTRS city=M".to_string())
+            .unwrap();
+    let address_said = address.said.clone().unwrap().to_string();
+    let person = build_from_ocafile(format!(
+        "ADD ATTRIBUTE name=Text address=refs:{}",
+        address_said
+    ))
+    .unwrap();
+    let refs = HashMap::from([(address_said.clone(), address)]);
+
+    assert!(matches!(
+        validate_data_with_refs(&person, &json!({"address": {"city": "Berlin"}}), &refs).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data_with_refs(&person, &json!({"address": {}}), &refs).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.len() == 1
+                && errors[0].to_string() == "address: Attribute \"city\" value is mandatory"
+    ));
+    assert!(matches!(
+        validate_data_with_refs(&person, &json!({"address": {}}), &HashMap::new()),
+        Err(OcaSdkError::MissingReference { said }) if said == address_said
+    ));
+}
+
+#[test]
+fn structured_validation_errors() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS name=M".to_string(),
+    )
+    .unwrap();
+
+    let DataValidationStatus::Invalid(errors) =
+        validate_data(&bundle, &json!({"age": "ten"})).unwrap()
+    else {
+        panic!("expected validation errors");
+    };
+    assert_eq!(errors.len(), 2);
+    assert!(errors.contains(&DataValidationError::MandatoryMissing {
+        attribute: "name".to_string()
+    }));
+
+    let mismatch = errors
+        .iter()
+        .find(|e| matches!(e, DataValidationError::TypeMismatch { .. }))
+        .unwrap();
+    assert_eq!(mismatch.attribute(), "age");
+    assert_eq!(
+        mismatch.to_string(),
+        "Attribute \"age\" value (\"ten\") is not a number"
+    );
+}
+
+#[test]
+fn validate_uris() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE website=Text iri=Text\nADD FORMAT ATTRS website=\"URI\" iri=\"IRI\""
+            .to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(
+            &bundle,
+            &json!({"website": "https://example.com/a", "iri": "https://例え.jp/パス"})
+        )
+        .unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(
+            &bundle,
+            &json!({"website": "not a uri", "iri": "https://例え.jp/パス"})
+        )
+        .unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"website\" value (\"not a uri\") is not a valid URI"])
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"website": "https://例え.jp/"})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn validate_array_elements() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE scores=Array[Numeric] matrix=Array[Array[Numeric]]".to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"scores": [], "matrix": [[1], ["a"]]})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"scores": [1, 2, "bad"]})).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"scores\" element at index 2 (\"bad\") is not a number"])
+    ));
+}
+
+#[test]
+fn validate_datetimes() {
+    let bundle = build_from_ocafile("ADD ATTRIBUTE issued_at=DateTime".to_string()).unwrap();
+
+    for datetime in [
+        "2024-05-01T12:30:00Z",
+        "2024-05-01T12:30:00.5+02:00",
+        "2024-05-01",
+    ] {
+        assert!(matches!(
+            validate_data(&bundle, &json!({ "issued_at": datetime })).unwrap(),
+            DataValidationStatus::Valid
+        ));
+    }
+    assert!(matches!(
+        validate_data(&bundle, &json!({"issued_at": "not a date"})).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"issued_at\" value (\"not a date\") is not a valid DateTime"])
+    ));
+
+    let legacy = ValidationOptions {
+        datetime_formats: Some(vec![DateTimeFormat::Custom("%d/%m/%Y".to_string())]),
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_data_with_options(&bundle, &json!({"issued_at": "01/05/2024"}), &legacy).unwrap(),
+        DataValidationStatus::Valid
+    ));
+
+    let hinted = build_from_ocafile(
+        "ADD ATTRIBUTE birth_date=DateTime\nADD FORMAT ATTRS birth_date=\"YYYY-MM-DD\"".to_string(),
+    )
+    .unwrap();
+    assert!(matches!(
+        validate_data(&hinted, &json!({"birth_date": "1990-04-12"})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&hinted, &json!({"birth_date": "1990-04-12T10:00:00Z"})).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"birth_date\" value (\"1990-04-12T10:00:00Z\") is not a valid DateTime of format \"YYYY-MM-DD\""])
+    ));
+}
+
+#[test]
+fn validate_arrays_of_referenced_objects() {
+    let item =
+        build_from_ocafile("ADD ATTRIBUTE sku=Text\nADD CONFORMANCE ATTRS sku=M".to_string())
+            .unwrap();
+    let item_said = item.said.clone().unwrap().to_string();
+    let order =
+        build_from_ocafile(format!("ADD ATTRIBUTE items=Array[refs:{}]", item_said)).unwrap();
+    let refs = HashMap::from([(item_said, item)]);
+
+    assert!(matches!(
+        validate_data_with_refs(&order, &json!({"items": [{"sku": "a1"}, {}]}), &refs).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["items[1]: Attribute \"sku\" value is mandatory"])
+    ));
+}
+
+#[test]
+fn validate_format_patterns() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE phone=Text\nADD FORMAT ATTRS phone=\"^\\+?[0-9]{7,15}$\"".to_string(),
+    )
+    .unwrap();
+    assert_eq!(bundle.info().format("phone"), Some("^\\+?[0-9]{7,15}$"));
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"phone": "+48123456789"})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"phone": "abc"})).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"phone\" value (\"abc\") does not match format \"^\\+?[0-9]{7,15}$\""])
+    ));
+
+    let invalid =
+        build_from_ocafile("ADD ATTRIBUTE phone=Text\nADD FORMAT ATTRS phone=\"[0-9\"".to_string())
+            .unwrap();
+    assert!(validate_data(&invalid, &json!({"phone": "1"})).is_err());
+}
+
+#[test]
+fn validate_more_format_patterns_than_cached() {
+    let bundles: Vec<_> = (1..=200)
+        .map(|n| {
+            build_from_ocafile(format!(
+                "ADD ATTRIBUTE code=Text\nADD FORMAT ATTRS code=\"^x{{{}}}$\"",
+                n
+            ))
+            .unwrap()
+        })
+        .collect();
+
+    // The patterns of the first bundles are evicted from the cache by the time they are reused.
+    for _ in 0..2 {
+        for (n, bundle) in (1..).zip(&bundles) {
+            assert!(matches!(
+                validate_data(bundle, &json!({"code": "x".repeat(n)})).unwrap(),
+                DataValidationStatus::Valid
+            ));
+            assert!(matches!(
+                validate_data(bundle, &json!({"code": "x".repeat(n + 1)})).unwrap(),
+                DataValidationStatus::Invalid(errors) if errors.len() == 1
+            ));
+        }
+    }
+}
+
+#[test]
+fn json_pointer_paths_of_errors() {
+    let address = build_from_ocafile(
+        "ADD ATTRIBUTE postal_code=Text\nADD CONFORMANCE ATTRS postal_code=M".to_string(),
+    )
+    .unwrap();
+    let address_said = address.said.clone().unwrap().to_string();
+    let person = build_from_ocafile(format!(
+        "ADD ATTRIBUTE address=refs:{}\nADD ATTRIBUTE scores=Array[Numeric]",
+        address_said
+    ))
+    .unwrap();
+    let refs = HashMap::from([(address_said, address)]);
+
+    let status = validate_data_with_refs(
+        &person,
+        &json!({"address": {}, "scores": [1, 2, 3, "x"]}),
+        &refs,
+    )
+    .unwrap();
+    let DataValidationStatus::Invalid(errors) = status else {
+        panic!("expected invalid data");
+    };
+    let mut paths: Vec<_> = errors.iter().map(|e| e.path()).collect();
+    paths.sort();
+    assert_eq!(paths, ["/address/postal_code", "/scores/3"]);
+}
+
+#[test]
+fn validate_records_in_batch() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS name=M".to_string(),
+    )
+    .unwrap();
+    let records = [
+        json!({"name": "Alice", "age": 30}),
+        json!({"age": "thirty"}),
+        json!({"name": "Bob"}),
+        json!({"name": 1}),
+    ];
+
+    let results = validate_batch(&bundle, records.iter());
+    assert_eq!(results.len(), 4);
+    assert!(matches!(results[0], Ok(DataValidationStatus::Valid)));
+    assert!(matches!(&results[1], Ok(DataValidationStatus::Invalid(errors)) if errors.len() == 2));
+    assert!(matches!(results[2], Ok(DataValidationStatus::Valid)));
+    assert!(matches!(results[3], Ok(DataValidationStatus::Invalid(_))));
+
+    assert!(matches!(
+        validate_batch_fail_fast(&bundle, records.iter()).unwrap(),
+        Some((1, errors)) if errors.len() == 2
+    ));
+    assert!(matches!(
+        validate_batch_fail_fast(&bundle, records.iter().step_by(2)).unwrap(),
+        None
+    ));
+}
+
+#[test]
+fn reject_unknown_attributes_in_strict_mode() {
+    let address = build_from_ocafile("ADD ATTRIBUTE city=Text".to_string()).unwrap();
+    let address_said = address.said.clone().unwrap().to_string();
+    let person = build_from_ocafile(format!(
+        "ADD ATTRIBUTE name=Text address=refs:{}",
+        address_said
+    ))
+    .unwrap();
+    let refs = HashMap::from([(address_said, address)]);
+    let data = json!({"nmae": "Alice", "address": {"cty": "Berlin"}});
+
+    assert!(matches!(
+        validate_data_with_refs(&person, &data, &refs).unwrap(),
+        DataValidationStatus::Valid
+    ));
+
+    let strict = ValidationOptions {
+        strict_unknown: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_data_with_options(&person, &data, &strict).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Unexpected attribute \"nmae\" not present in schema"])
+    ));
+    let DataValidationStatus::Invalid(errors) =
+        validate_data_with_refs_and_options(&person, &data, &refs, &strict).unwrap()
+    else {
+        panic!("expected unknown attributes to be reported");
+    };
+    let mut messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        [
+            "Unexpected attribute \"nmae\" not present in schema",
+            "address: Unexpected attribute \"cty\" not present in schema",
+        ]
+    );
+}
+
+#[test]
+fn hint_at_booleans_passed_as_strings() {
+    let bundle =
+        build_from_ocafile("ADD ATTRIBUTE passed=Boolean flags=Array[Boolean]".to_string())
+            .unwrap();
+
+    let DataValidationStatus::Invalid(errors) = validate_data(
+        &bundle,
+        &json!({"passed": "true", "flags": [true, "false"]}),
+    )
+    .unwrap() else {
+        panic!("expected booleans passed as strings to be rejected");
+    };
+    let mut messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        [
+            "Attribute \"flags\" element at index 1 (\"false\") is not a boolean. Did you mean to pass a boolean (true/false) rather than the string \"false\"?",
+            "Attribute \"passed\" value (\"true\") is not a boolean. Did you mean to pass a boolean (true/false) rather than the string \"true\"?",
+        ]
+    );
+    assert!(matches!(
+        validate_data(&bundle, &json!({"passed": "yes"})).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"passed\" value (\"yes\") is not a boolean"])
+    ));
+}
+
+#[test]
+fn cap_number_of_errors() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE a=Numeric b=Numeric c=Numeric d=Numeric\nADD CONFORMANCE ATTRS a=M b=M c=M d=M"
+            .to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 4
+    ));
+
+    let options = ValidationOptions {
+        max_errors: Some(2),
+        ..Default::default()
+    };
+    let DataValidationStatus::Invalid(errors) =
+        validate_data_with_options(&bundle, &json!({}), &options).unwrap()
+    else {
+        panic!("expected missing attributes to be reported");
+    };
+    // Validation stops at the third attribute, so the fourth one isn't counted.
+    assert_eq!(errors.len(), 3);
+    assert_eq!(errors[0].attribute(), "a");
+    assert_eq!(errors[1].attribute(), "b");
+    assert_eq!(
+        errors[2].to_string(),
+        "... (at least 1 more error suppressed)"
+    );
+
+    let detailed = validate_data_detailed(&bundle, &json!({}), &options).unwrap();
+    assert_eq!(detailed.len(), 3);
+    assert_eq!(detailed[0].attribute, "a");
+    assert_eq!(detailed[1].attribute, "b");
+    assert_eq!(
+        detailed[2].message,
+        "... (at least 1 more error suppressed)"
+    );
+    assert_eq!(detailed[2].overlay_said, None);
+
+    let strict = ValidationOptions {
+        strict_unknown: true,
+        ..options
+    };
+    assert!(matches!(
+        validate_data_with_options(&bundle, &json!({"e": 1}), &strict).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.len() == 3 && errors.iter().all(|e| e.attribute() != "e")
+    ));
+}
+
+#[test]
+fn forbid_surrounding_whitespace_of_codes() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE country=Text note=Text\nADD FORMAT ATTRS country=\"[A-Z]{2}\"".to_string(),
+    )
+    .unwrap();
+    let data = json!({"country": " PL", "note": " Hello "});
+
+    assert!(matches!(
+        validate_data(&bundle, &data).unwrap(),
+        DataValidationStatus::Valid
+    ));
+
+    let options = ValidationOptions {
+        forbid_surrounding_whitespace: true,
+        ..Default::default()
+    };
+    assert!(matches!(
+        validate_data_with_options(&bundle, &data, &options).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"country\" value (\" PL\") has leading/trailing whitespace"])
+    ));
+}
+
+#[test]
+fn normalize_text_codes() {
+    let bundle = build_from_ocafile(
+        r#"ADD ATTRIBUTE country=Text born=DateTime note=Text
+ADD FORMAT ATTRS country="[A-Z]{2}" born="YYYY-MM-DD""#
+            .to_string(),
+    )
+    .unwrap();
+
+    assert_eq!(
+        normalize_data(
+            &bundle,
+            &json!({"country": " PL ", "born": " 1990-04-12", "note": " Hello "})
+        ),
+        json!({"country": "PL", "born": " 1990-04-12", "note": " Hello "})
+    );
+}
+
+#[test]
+fn validate_partial_data() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS name=M age=M".to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data_partial(&bundle, &json!({"age": 30, "nmae": "Alice"}), false).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data_partial(&bundle, &json!({"name": null, "age": "thirty"}), false).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"age\" value (\"thirty\") is not a number"])
+    ));
+    assert!(matches!(
+        validate_data_partial(&bundle, &json!({"age": 30, "nmae": "Alice"}), true).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Unexpected attribute \"nmae\" not present in schema"])
+    ));
+}
+
+#[test]
+fn validate_many_records() {
+    let bundle =
+        build_from_ocafile("ADD ATTRIBUTE name=Text\nADD CONFORMANCE ATTRS name=M".to_string())
+            .unwrap();
+    let statuses =
+        validate_data_many(&bundle, &[json!({"name": "Alice"}), json!({}), json!([])]).unwrap();
+    assert!(matches!(
+        statuses.as_slice(),
+        [
+            DataValidationStatus::Valid,
+            DataValidationStatus::Invalid(_),
+            DataValidationStatus::Invalid(_)
+        ]
+    ));
+
+    let invalid =
+        build_from_ocafile("ADD ATTRIBUTE phone=Text\nADD FORMAT ATTRS phone=\"[0-9\"".to_string())
+            .unwrap();
+    assert!(matches!(
+        validate_data_many(&invalid, &[json!({}), json!({"phone": "1"})]),
+        Err(OcaSdkError::Record { index: 1, .. })
+    ));
+}
+
+#[test]
+fn reuse_data_validator() {
+    let bundle =
+        build_from_ocafile("ADD ATTRIBUTE name=Text\nADD CONFORMANCE ATTRS name=M".to_string())
+            .unwrap();
+    let validator = DataValidator::new(&bundle).unwrap();
+    let errors = |status: DataValidationStatus| match status {
+        DataValidationStatus::Valid => vec![],
+        DataValidationStatus::Invalid(errors) => errors,
+    };
+
+    for data in [json!({"name": "Alice"}), json!({}), json!({"name": 1})] {
+        assert_eq!(
+            errors(validator.validate(&data).unwrap()),
+            errors(validate_data(&bundle, &data).unwrap())
+        );
+    }
+
+    let strict = DataValidator::with_options(
+        &bundle,
+        ValidationOptions {
+            strict_unknown: true,
+            ..Default::default()
+        },
+    )
+    .unwrap();
+    assert!(matches!(
+        strict.validate(&json!({"name": "Alice", "age": 1})).unwrap(),
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+}
+
+#[test]
+fn validate_form_field() -> Result<(), Box<dyn std::error::Error>> {
+    let structural_bundle_str =
+        fs::read_to_string(Path::new("tests/assets/semantics/structural_bundle.json"))?;
+    let structural_bundle = load(&mut structural_bundle_str.as_bytes()).unwrap();
+
+    assert!(matches!(
+        validate_field(&structural_bundle, "passed", &json!(null))?,
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_field(&structural_bundle, "passed", &json!("yes"))?,
+        DataValidationStatus::Invalid(errors) if errors.len() == 1
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn record_validation_timing() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric\nADD CONFORMANCE ATTRS name=M".to_string(),
+    )
+    .unwrap();
+    let data = json!({"age": 42});
+    let options = ValidationOptions {
+        record_timing: true,
+        ..Default::default()
+    };
+
+    let report = validate_data_with_report(&bundle, &data, &options).unwrap();
+    assert!(
+        matches!(report.status, DataValidationStatus::Invalid(ref errors) if errors.len() == 1)
+    );
+    let mut names: Vec<_> = report.timings.keys().cloned().collect();
+    names.sort();
+    assert_eq!(names, vec!["age".to_string(), "name".to_string()]);
+
+    let report = validate_data_with_report(&bundle, &data, &ValidationOptions::default()).unwrap();
+    assert!(report.timings.is_empty());
+}
+
+#[test]
+fn validate_numeric_ranges() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE age=Numeric score=Numeric\n\
+         ADD FORMAT ATTRS age='{\"min\": 0, \"max\": 120}' score='{\"min\": 0}'"
+            .to_string(),
+    )
+    .unwrap();
+    assert_eq!(
+        bundle.info().numeric_range("age"),
+        Some(NumericRange {
+            min: Some(0.0),
+            max: Some(120.0)
+        })
+    );
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"age": 120, "score": 1000})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    let DataValidationStatus::Invalid(errors) =
+        validate_data(&bundle, &json!({"age": 250, "score": -1})).unwrap()
+    else {
+        panic!("expected values out of range");
+    };
+    let mut messages: Vec<_> = errors.iter().map(|e| e.to_string()).collect();
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            "Attribute \"age\" value (250) exceeds maximum 120",
+            "Attribute \"score\" value (-1) is below minimum 0",
+        ]
+    );
+}
+
+#[test]
+fn check_ranges_of_numeric_attributes_only() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE price=Numeric code=Text\n\
+         ADD FORMAT ATTRS price='{\"precision\": 2}' code='{\"max\": 10}'"
+            .to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"price": 1e9, "code": "A1"})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"code": 100})).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if matches!(errors.as_slice(), [DataValidationError::TypeMismatch { .. }])
+    ));
+}
+
+#[test]
+fn explain_errors_with_labels() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE age=Numeric name=Text\n\
+         ADD CONFORMANCE ATTRS name=M\n\
+         ADD LABEL en ATTRS age=\"Age\""
+            .to_string(),
+    )
+    .unwrap();
+
+    let explanation = validate_data_and_explain(&bundle, &json!({"age": "x"}), "en").unwrap();
+    assert!(matches!(
+        explanation.status,
+        DataValidationStatus::Invalid(_)
+    ));
+    let mut messages = explanation.messages;
+    messages.sort();
+    assert_eq!(
+        messages,
+        vec![
+            "Age value (\"x\") is not a number",
+            "Attribute \"name\" value is mandatory",
+        ]
+    );
+
+    let explanation =
+        validate_data_and_explain(&bundle, &json!({"age": 1, "name": "Jo"}), "en").unwrap();
+    assert!(explanation.messages.is_empty());
+}
+
+#[test]
+fn validate_base64_binary_values() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE photo=Binary\nADD FORMAT ATTRS photo='{\"max_size\": 4}'".to_string(),
+    )
+    .unwrap();
+    let messages = |data: serde_json::Value, options: &ValidationOptions| {
+        let status = validate_data_with_options(&bundle, &data, options).unwrap();
+        match status {
+            DataValidationStatus::Valid => vec![],
+            DataValidationStatus::Invalid(errors) => {
+                errors.iter().map(|e| e.to_string()).collect::<Vec<_>>()
+            }
+        }
+    };
+    let options = ValidationOptions::default();
+
+    assert!(messages(json!({"photo": "aGVsbG8="}), &options).is_empty());
+    assert!(messages(json!({"photo": "_-8"}), &options).is_empty());
+    assert_eq!(
+        messages(json!({"photo": "not base64!"}), &options),
+        vec!["Attribute \"photo\" value is not valid base64"]
+    );
+
+    let options = ValidationOptions {
+        enforce_binary_size: true,
+        ..Default::default()
+    };
+    assert!(messages(json!({"photo": "aGk="}), &options).is_empty());
+    assert_eq!(
+        messages(json!({"photo": "aGVsbG8="}), &options),
+        vec!["Attribute \"photo\" value of 5 bytes exceeds maximum size of 4 bytes"]
+    );
+}
+
+#[test]
+fn match_format_patterns_like_regex_search() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE code=Text exact=Text name=Text\n\
+         ADD FORMAT ATTRS code=\"[0-9]{3}\" exact=\"^[0-9]{3}$\" name=\"^\\w+$\""
+            .to_string(),
+    )
+    .unwrap();
+    let failing = |data: serde_json::Value| match validate_data(&bundle, &data).unwrap() {
+        DataValidationStatus::Valid => vec![],
+        DataValidationStatus::Invalid(errors) => errors
+            .iter()
+            .map(|e| e.attribute().to_string())
+            .collect::<Vec<_>>(),
+    };
+
+    // Patterns without anchors match any part of the value.
+    assert!(failing(json!({"code": "abc123def"})).is_empty());
+    assert_eq!(failing(json!({"exact": "abc123def"})), vec!["exact"]);
+    assert!(failing(json!({"exact": "123"})).is_empty());
+
+    // Character classes are Unicode-aware.
+    assert!(failing(json!({"name": "Zażółć"})).is_empty());
+    assert_eq!(failing(json!({"name": "Zażółć gęślą"})), vec!["name"]);
+
+    let invalid = build_from_ocafile(
+        "ADD ATTRIBUTE code=Text\nADD FORMAT ATTRS code=\"(unclosed\"".to_string(),
+    )
+    .unwrap();
+    // The invalid pattern is a defect of the bundle, skipped by the data validation.
+    assert!(matches!(
+        validate_data(&invalid, &json!({"code": "x"})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_semantics(&invalid).unwrap(),
+        SemanticValidationStatus::Invalid(errors)
+            if errors.len() == 1
+                && errors[0].to_string().starts_with(
+                    "Format of attribute \"code\" is not a valid regular expression"
+                )
+    ));
+}
+
+#[test]
+fn validate_integer_attributes() {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE count=Numeric price=Numeric\n\
+         ADD FORMAT ATTRS count='{\"integer\": true, \"min\": 0}'"
+            .to_string(),
+    )
+    .unwrap();
+
+    assert!(matches!(
+        validate_data(&bundle, &json!({"count": 3, "price": 3.5})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"count": 3.0})).unwrap(),
+        DataValidationStatus::Valid
+    ));
+    assert!(matches!(
+        validate_data(&bundle, &json!({"count": 3.5})).unwrap(),
+        DataValidationStatus::Invalid(errors)
+            if errors.iter().map(|e| e.to_string()).eq(["Attribute \"count\" value (3.5) is not an integer"])
+    ));
+}
+
+#[test]
+fn reject_skeleton_bundles() {
+    let skeleton = skeleton_bundle("EGQbKoJjLEcAfjkg3iW9tpwmnmidqxActG-dB0onEvqw").unwrap();
+    let data = json!({"name": "Alice"});
+
+    assert!(matches!(
+        validate_data(&skeleton, &data),
+        Err(OcaSdkError::SkeletonBundle(_))
+    ));
+    assert!(matches!(
+        DataValidator::new(&skeleton),
+        Err(OcaSdkError::SkeletonBundle(_))
+    ));
+    assert!(matches!(
+        validate_field(&skeleton, "name", &json!("Alice")),
+        Err(OcaSdkError::SkeletonBundle(_))
+    ));
+    assert!(validate_batch(&skeleton, [data.clone(), data].iter())
+        .into_iter()
+        .all(|status| matches!(status, Err(OcaSdkError::SkeletonBundle(_)))));
+}
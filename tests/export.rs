@@ -0,0 +1,139 @@
+use oca_sdk_rs::{
+    build_from_ocafile,
+    conversion::{build_from_json_schema, to_json_schema},
+    export::graphql::to_graphql_schema,
+    WithInfo,
+};
+use serde_json::json;
+
+#[test]
+fn export_graphql_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric tags=Array[Text]\nADD CONFORMANCE ATTRS name=M"
+            .to_string(),
+    )?;
+    let schema = to_graphql_schema(&bundle)?;
+
+    let name = bundle.said.unwrap().to_string().replace('-', "_");
+    let fields = "  age: Float\n  name: String!\n  tags: [String]\n";
+    assert_eq!(
+        schema,
+        format!("type {name} {{\n{fields}}}\n\ninput {name}Input {{\n{fields}}}\n")
+    );
+
+    Ok(())
+}
+
+#[test]
+fn export_json_schema() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text born=DateTime photo=Binary score=Numeric active=Boolean \
+         tags=Array[Text]\n\
+         ADD CONFORMANCE ATTRS name=M score=M\n\
+         ADD ENTRY_CODE ATTRS tags=[\"a\", \"b\"]"
+            .to_string(),
+    )?;
+
+    assert_eq!(
+        to_json_schema(&bundle),
+        json!({
+            "$schema": "http://json-schema.org/draft-07/schema#",
+            "type": "object",
+            "properties": {
+                "active": {"type": "boolean"},
+                "born": {"type": "string", "format": "date-time"},
+                "name": {"type": "string"},
+                "photo": {"type": "string", "contentEncoding": "base64"},
+                "score": {"type": "number"},
+                "tags": {"type": "array", "items": {"type": "string", "enum": ["a", "b"]}},
+            },
+            "required": ["name", "score"],
+        })
+    );
+
+    let converted = build_from_json_schema(&to_json_schema(&bundle).to_string())?;
+    let info = converted.info();
+    assert_eq!(
+        info.attribute("tags").unwrap().attribute_type,
+        bundle.info().attribute("tags").unwrap().attribute_type
+    );
+    assert_eq!(
+        info.attribute("score").unwrap().conformance.as_deref(),
+        Some("M")
+    );
+
+    Ok(())
+}
+
+#[cfg(feature = "parquet")]
+#[test]
+fn export_parquet_schema() -> Result<(), Box<dyn std::error::Error>> {
+    use oca_sdk_rs::{bundle::skeleton_bundle, export::parquet::to_parquet_schema, OcaSdkError};
+    use parquet::{
+        basic::{LogicalType, Repetition, Type as PhysicalType},
+        schema::parser::parse_message_type,
+    };
+
+    let bundle = build_from_ocafile(
+        "ADD ATTRIBUTE name=Text age=Numeric active=Boolean photo=Binary tags=Array[Text]\n\
+         ADD CONFORMANCE ATTRS name=M"
+            .to_string(),
+    )?;
+    let schema = parse_message_type(&String::from_utf8(to_parquet_schema(&bundle)?)?)?;
+    assert_eq!(schema.name(), bundle.said.unwrap().to_string());
+
+    let fields = schema.get_fields();
+    assert_eq!(
+        fields.iter().map(|field| field.name()).collect::<Vec<_>>(),
+        ["active", "age", "name", "photo", "tags"]
+    );
+    for (field, physical_type, logical_type, repetition) in [
+        (
+            &fields[0],
+            PhysicalType::BOOLEAN,
+            None,
+            Repetition::OPTIONAL,
+        ),
+        (&fields[1], PhysicalType::DOUBLE, None, Repetition::OPTIONAL),
+        (
+            &fields[2],
+            PhysicalType::BYTE_ARRAY,
+            Some(LogicalType::String),
+            Repetition::REQUIRED,
+        ),
+        (
+            &fields[3],
+            PhysicalType::BYTE_ARRAY,
+            None,
+            Repetition::OPTIONAL,
+        ),
+    ] {
+        assert_eq!(field.get_physical_type(), physical_type, "{}", field.name());
+        assert_eq!(field.get_basic_info().logical_type(), logical_type);
+        assert_eq!(field.get_basic_info().repetition(), repetition);
+    }
+
+    let tags = &fields[4];
+    assert!(tags.is_group());
+    assert_eq!(
+        tags.get_basic_info().logical_type(),
+        Some(LogicalType::List)
+    );
+    assert_eq!(tags.get_basic_info().repetition(), Repetition::OPTIONAL);
+    let list = &tags.get_fields()[0];
+    assert_eq!(list.get_basic_info().repetition(), Repetition::REPEATED);
+    let element = &list.get_fields()[0];
+    assert_eq!(element.get_physical_type(), PhysicalType::BYTE_ARRAY);
+    assert_eq!(
+        element.get_basic_info().logical_type(),
+        Some(LogicalType::String)
+    );
+
+    let skeleton = skeleton_bundle("EGQbKoJjLEcAfjkg3iW9tpwmnmidqxActG-dB0onEvqw")?;
+    assert!(matches!(
+        to_parquet_schema(&skeleton),
+        Err(OcaSdkError::SkeletonBundle(_))
+    ));
+
+    Ok(())
+}
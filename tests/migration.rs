@@ -0,0 +1,41 @@
+use oca_sdk_rs::{
+    build_from_ocafile,
+    migration::{migrate_data, MigrationError},
+};
+use serde_json::json;
+
+const FRAMING: &str = r#"ADD ATTR_FRAMING id="v1" ATTRS last_name={"surname": {"predicate_id": "skos:exactMatch", "framing_justification": "semapv:ManualMappingCuration"}}"#;
+
+#[test]
+fn migrate_renamed_and_removed_attributes() {
+    let old = build_from_ocafile("ADD ATTRIBUTE surname=Text age=Numeric legacy=Text".to_string())
+        .unwrap();
+    let new = build_from_ocafile(format!(
+        "ADD ATTRIBUTE last_name=Text age=Numeric\n{}",
+        FRAMING
+    ))
+    .unwrap();
+
+    let data = json!({"surname": "Doe", "age": 30, "legacy": "x", "extra": true});
+    assert_eq!(
+        migrate_data(&old, &new, &data).unwrap(),
+        json!({"last_name": "Doe", "age": 30, "extra": true})
+    );
+}
+
+#[test]
+fn migrate_new_mandatory_attributes() {
+    let old = build_from_ocafile("ADD ATTRIBUTE surname=Text".to_string()).unwrap();
+    let new = build_from_ocafile(
+        "ADD ATTRIBUTE surname=Text email=Text\nADD CONFORMANCE ATTRS email=M".to_string(),
+    )
+    .unwrap();
+
+    match migrate_data(&old, &new, &json!({"surname": "Doe"})) {
+        Err(MigrationError::NewMandatoryAttributes { attributes, data }) => {
+            assert_eq!(attributes, vec!["email".to_string()]);
+            assert_eq!(data, json!({"surname": "Doe", "email": null}));
+        }
+        other => panic!("Unexpected migration result: {:?}", other),
+    }
+}
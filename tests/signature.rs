@@ -0,0 +1,29 @@
+#![cfg(feature = "signature")]
+use oca_sdk_rs::{
+    build_from_ocafile,
+    signature::{bundle_signature_sign, bundle_signature_verify},
+};
+
+#[test]
+fn sign_and_verify_bundle() -> Result<(), Box<dyn std::error::Error>> {
+    let bundle = build_from_ocafile("ADD ATTRIBUTE name=Text".to_string()).unwrap();
+    let other = build_from_ocafile("ADD ATTRIBUTE name=Numeric".to_string()).unwrap();
+
+    let private_key = [7u8; 32];
+    let public_key = ed25519_dalek::SigningKey::from_bytes(&private_key).verifying_key();
+
+    let signature = bundle_signature_sign(&bundle, &private_key)?;
+    assert!(bundle_signature_verify(
+        &bundle,
+        &signature,
+        public_key.as_bytes()
+    )?);
+    assert!(!bundle_signature_verify(
+        &other,
+        &signature,
+        public_key.as_bytes()
+    )?);
+    assert!(bundle_signature_verify(&bundle, &signature, &[0u8; 3]).is_err());
+
+    Ok(())
+}
@@ -0,0 +1,52 @@
+#![cfg(feature = "test-data")]
+use oca_sdk_rs::{
+    build_from_ocafile,
+    data_validator::{validate_data, DataValidationStatus},
+    test_data::{generate_test_data, TestDataOptions},
+};
+use std::fs;
+use std::path::Path;
+
+#[test]
+fn generated_test_data_is_valid() -> Result<(), Box<dyn std::error::Error>> {
+    let ocafile_str = fs::read_to_string(Path::new(
+        "tests/assets/semantics/entrance_credential.ocafile",
+    ))?;
+    let oca_bundle = build_from_ocafile(ocafile_str).unwrap();
+
+    let options = TestDataOptions { seed: Some(7) };
+    let data = generate_test_data(&oca_bundle, options.clone());
+
+    assert_eq!(data, generate_test_data(&oca_bundle, options));
+    assert!(matches!(
+        validate_data(&oca_bundle, &data)?,
+        DataValidationStatus::Valid
+    ));
+
+    Ok(())
+}
+
+#[test]
+fn generated_test_data_follows_formats() -> Result<(), Box<dyn std::error::Error>> {
+    let oca_bundle = build_from_ocafile(
+        r#"ADD ATTRIBUTE born=DateTime time=DateTime email=Text code=Text id=Text site=Text
+ADD ATTRIBUTE currency=Text country=Text age=Numeric ratio=Numeric debt=Numeric
+ADD FORMAT ATTRS born="YYYY-MM-DD" time="hh:mm A" email="email" code="^[A-Z]{3}-[0-9]{4}$" id="UUID" site="URI" currency="ISO4217" country="ISO3166-1-alpha-2"
+ADD FORMAT ATTRS age='{"min": 18, "max": 65, "integer": true}' ratio='{"min": 0.5, "max": 0.6}' debt='{"max": -10}'"#
+            .to_string(),
+    )
+    .unwrap();
+
+    for seed in 0..20 {
+        let data = generate_test_data(&oca_bundle, TestDataOptions { seed: Some(seed) });
+        if let DataValidationStatus::Invalid(errors) = validate_data(&oca_bundle, &data)? {
+            panic!("{} is invalid: {:?}", data, errors);
+        }
+
+        let age = data["age"].as_i64().unwrap();
+        assert!((18..=65).contains(&age));
+        assert_eq!(data["born"].as_str().unwrap().len(), "YYYY-MM-DD".len());
+    }
+
+    Ok(())
+}